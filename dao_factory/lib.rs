@@ -0,0 +1,156 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod dao_factory {
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+    use ink::{ToAddr, H160, H256};
+    use simple_dao::SimpleDaoRef;
+
+    const MAX_PAGE_SIZE: u32 = 100;
+
+    #[ink(storage)]
+    pub struct DaoFactory {
+        owner: H160,
+        dao_code_hash: H256,      // Uploaded `SimpleDao` code new DAOs run
+        daos: Mapping<u32, H160>, // Deployment index -> DAO
+        dao_count: u32,
+        creators: Mapping<H160, H160>, // DAO -> account that deployed it
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DaoCreated {
+        #[ink(topic)]
+        dao: H160,
+        #[ink(topic)]
+        creator: H160,
+        index: u32,
+        code_hash: H256,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DaoCodeHashChanged {
+        code_hash: H256,
+    }
+
+    // Custom errors
+    #[derive(Debug, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        NotOwner,
+        InstantiationFailed,
+        InvalidDaoConfig(simple_dao::Error), // The DAO constructor rejected the parameters
+    }
+
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    impl DaoFactory {
+        /// Initialize the factory with the code hash of an uploaded `SimpleDao`
+        #[ink(constructor)]
+        pub fn new(dao_code_hash: H256) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                dao_code_hash,
+                daos: Mapping::new(),
+                dao_count: 0,
+                creators: Mapping::new(),
+            }
+        }
+
+        /// Deploy a new DAO with the given founding members and voting rules. Any value sent
+        /// along seeds its treasury.
+        #[ink(message, payable)]
+        pub fn create_dao(
+            &mut self,
+            members: Vec<H160>,
+            total_supply: Balance,
+            voting_period: u64,
+            min_votes_required: u32,
+        ) -> Result<H160> {
+            let creator = self.env().caller();
+            let index = self.dao_count;
+
+            // The deployment index keeps every instance's address distinct
+            let mut salt = [0u8; 32];
+            salt[..4].copy_from_slice(&index.to_be_bytes());
+
+            let dao =
+                SimpleDaoRef::try_new(members, total_supply, voting_period, min_votes_required)
+                    .code_hash(self.dao_code_hash)
+                    .endowment(self.env().transferred_value())
+                    .salt_bytes(Some(salt))
+                    .try_instantiate()
+                    .map_err(|_| Error::InstantiationFailed)?
+                    .map_err(|_| Error::InstantiationFailed)?
+                    .map_err(Error::InvalidDaoConfig)?
+                    .to_addr();
+
+            self.daos.insert(index, &dao);
+            self.creators.insert(dao, &creator);
+            self.dao_count += 1;
+
+            Self::env().emit_event(DaoCreated {
+                dao,
+                creator,
+                index,
+                code_hash: self.dao_code_hash,
+            });
+
+            Ok(dao)
+        }
+
+        /// Point new deployments at another uploaded version of `SimpleDao`
+        #[ink(message)]
+        pub fn set_dao_code_hash(&mut self, code_hash: H256) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.dao_code_hash = code_hash;
+
+            Self::env().emit_event(DaoCodeHashChanged { code_hash });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_dao_code_hash(&self) -> H256 {
+            self.dao_code_hash
+        }
+
+        #[ink(message)]
+        pub fn get_owner(&self) -> H160 {
+            self.owner
+        }
+
+        #[ink(message)]
+        pub fn get_dao_count(&self) -> u32 {
+            self.dao_count
+        }
+
+        #[ink(message)]
+        pub fn get_dao(&self, index: u32) -> Option<H160> {
+            self.daos.get(index)
+        }
+
+        /// Up to `limit` deployed DAOs from index `start`, at most `MAX_PAGE_SIZE` per call
+        #[ink(message)]
+        pub fn get_daos(&self, start: u32, limit: u32) -> Vec<H160> {
+            let end = start
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(self.dao_count);
+            (start..end)
+                .filter_map(|index| self.daos.get(index))
+                .collect()
+        }
+
+        /// Account that deployed a DAO, `None` for DAOs not made by this factory
+        #[ink(message)]
+        pub fn get_creator(&self, dao: H160) -> Option<H160> {
+            self.creators.get(dao)
+        }
+    }
+}
+
+pub use self::dao_factory::*;
@@ -0,0 +1,133 @@
+//! End-to-end tests deploying DAOs through the factory on a node running `pallet-revive`,
+//! e.g. `ink-node` on the PATH or at `CONTRACTS_NODE`. Run with
+//! `cargo test -p dao_factory --features e2e-tests --test e2e_tests`.
+#![cfg(feature = "e2e-tests")]
+
+use dao_factory::*;
+use ink::env::DefaultEnvironment;
+use ink_e2e::{ContractsBackend, Sr25519Keyring};
+use simple_dao::{Dao, SimpleDao};
+
+type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+// Helper function to get the address of a dev account
+fn address(account: Sr25519Keyring) -> ink::H160 {
+    ink_e2e::address::<DefaultEnvironment>(account)
+}
+
+#[ink_e2e::test]
+async fn create_dao_records_each_deployment(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+    // given
+    let code_hash = client
+        .upload("simple_dao", &ink_e2e::alice())
+        .submit()
+        .await
+        .expect("upload failed")
+        .code_hash;
+    let mut constructor = DaoFactoryRef::new(code_hash);
+    let contract = client
+        .instantiate("dao_factory", &ink_e2e::alice(), &mut constructor)
+        .submit()
+        .await
+        .expect("instantiate failed");
+    let mut factory = contract.call_builder::<DaoFactory>();
+    let members = vec![address(Sr25519Keyring::Alice), address(Sr25519Keyring::Bob)];
+
+    // when
+    let mut daos = Vec::new();
+    for _ in 0..2 {
+        let create = client
+            .call(
+                &ink_e2e::bob(),
+                &factory.create_dao(members.clone(), 2000, 10, 1),
+            )
+            .submit()
+            .await
+            .expect("create_dao failed");
+        daos.push(create.return_value().expect("dao rejected"));
+    }
+
+    // then
+    // The deployment index salts each address, so equal parameters don't collide
+    assert_ne!(daos[0], daos[1]);
+    let count = client
+        .call(&ink_e2e::alice(), &factory.get_dao_count())
+        .dry_run()
+        .await?
+        .return_value();
+    assert_eq!(count, 2);
+    let listed = client
+        .call(&ink_e2e::alice(), &factory.get_daos(0, 10))
+        .dry_run()
+        .await?
+        .return_value();
+    assert_eq!(listed, daos);
+    let second = client
+        .call(&ink_e2e::alice(), &factory.get_dao(1))
+        .dry_run()
+        .await?
+        .return_value();
+    assert_eq!(second, Some(daos[1]));
+    let creator = client
+        .call(&ink_e2e::alice(), &factory.get_creator(daos[0]))
+        .dry_run()
+        .await?
+        .return_value();
+    assert_eq!(creator, Some(address(Sr25519Keyring::Bob)));
+
+    // The deployed DAO runs the uploaded code with the given members
+    let dao = ink_e2e::create_call_builder::<SimpleDao>(daos[0]);
+    let is_member = client
+        .call(
+            &ink_e2e::alice(),
+            &dao.is_member(address(Sr25519Keyring::Bob)),
+        )
+        .dry_run()
+        .await?
+        .return_value();
+    assert!(is_member);
+
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn create_dao_reports_invalid_config(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+    // given
+    let code_hash = client
+        .upload("simple_dao", &ink_e2e::alice())
+        .submit()
+        .await
+        .expect("upload failed")
+        .code_hash;
+    let mut constructor = DaoFactoryRef::new(code_hash);
+    let contract = client
+        .instantiate("dao_factory", &ink_e2e::alice(), &mut constructor)
+        .submit()
+        .await
+        .expect("instantiate failed");
+    let mut factory = contract.call_builder::<DaoFactory>();
+
+    // when
+    let result = client
+        .call(
+            &ink_e2e::alice(),
+            &factory.create_dao(Vec::new(), 2000, 10, 1),
+        )
+        .dry_run()
+        .await?
+        .return_value();
+
+    // then
+    assert_eq!(
+        result,
+        Err(Error::InvalidDaoConfig(simple_dao::Error::NoMembers))
+    );
+    let count = client
+        .call(&ink_e2e::alice(), &factory.get_dao_count())
+        .dry_run()
+        .await?
+        .return_value();
+    assert_eq!(count, 0);
+
+    Ok(())
+}
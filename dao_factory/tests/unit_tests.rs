@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use dao_factory::*;
+    use ink::env::test;
+    use ink::{H160, H256};
+
+    #[ink::test]
+    fn test_factory_creation() {
+        let owner = H160::from([1; 20]);
+        test::set_caller(owner);
+
+        let factory = DaoFactory::new(H256::from([7; 32]));
+
+        assert_eq!(factory.get_owner(), owner);
+        assert_eq!(factory.get_dao_code_hash(), H256::from([7; 32]));
+        assert_eq!(factory.get_dao_count(), 0);
+        assert_eq!(factory.get_dao(0), None);
+        assert!(factory.get_daos(0, 10).is_empty());
+        assert_eq!(factory.get_creator(H160::from([9; 20])), None);
+    }
+
+    #[ink::test]
+    fn test_only_owner_sets_dao_code_hash() {
+        let owner = H160::from([1; 20]);
+        let other = H160::from([2; 20]);
+        test::set_caller(owner);
+
+        let mut factory = DaoFactory::new(H256::from([7; 32]));
+
+        test::set_caller(other);
+        assert_eq!(
+            factory.set_dao_code_hash(H256::from([8; 32])),
+            Err(Error::NotOwner)
+        );
+
+        test::set_caller(owner);
+        factory.set_dao_code_hash(H256::from([8; 32])).unwrap();
+        assert_eq!(factory.get_dao_code_hash(), H256::from([8; 32]));
+    }
+}
@@ -0,0 +1,173 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod dao_registry {
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+    use ink::{H160, H256};
+    use simple_dao::Dao;
+
+    const MAX_PAGE_SIZE: u32 = 100;
+    const MAX_NAME_LENGTH: usize = 64;
+
+    /// Listing of a deployed DAO
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct DaoEntry {
+        pub dao: H160,
+        pub name: String,
+        pub metadata_hash: H256, // Hash of the off-chain description, e.g. an IPFS document
+        pub registrant: H160,
+        pub registered_at: u64,
+    }
+
+    #[ink(storage)]
+    pub struct DaoRegistry {
+        entries: Mapping<H160, DaoEntry>,
+        names: Mapping<String, H160>,   // Name -> DAO, names are unique
+        registered: Mapping<u32, H160>, // Registration index -> DAO
+        dao_count: u32,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DaoRegistered {
+        #[ink(topic)]
+        dao: H160,
+        name: String,
+        metadata_hash: H256,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DaoMetadataUpdated {
+        #[ink(topic)]
+        dao: H160,
+        metadata_hash: H256,
+    }
+
+    // Custom errors
+    #[derive(Debug, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        NotAuthorized,
+        AlreadyRegistered,
+        NotRegistered,
+        NameTaken,
+        InvalidName,
+    }
+
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    impl Default for DaoRegistry {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl DaoRegistry {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                entries: Mapping::new(),
+                names: Mapping::new(),
+                registered: Mapping::new(),
+                dao_count: 0,
+            }
+        }
+
+        /// List a DAO under a unique name. Either the DAO itself or one of its members may
+        /// register it.
+        #[ink(message)]
+        pub fn register(&mut self, dao: H160, name: String, metadata_hash: H256) -> Result<()> {
+            self.ensure_authorized(dao)?;
+            if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+                return Err(Error::InvalidName);
+            }
+            if self.entries.contains(dao) {
+                return Err(Error::AlreadyRegistered);
+            }
+            if self.names.contains(&name) {
+                return Err(Error::NameTaken);
+            }
+
+            let entry = DaoEntry {
+                dao,
+                name: name.clone(),
+                metadata_hash,
+                registrant: self.env().caller(),
+                registered_at: self.env().block_number() as u64,
+            };
+            self.entries.insert(dao, &entry);
+            self.names.insert(&name, &dao);
+            self.registered.insert(self.dao_count, &dao);
+            self.dao_count += 1;
+
+            Self::env().emit_event(DaoRegistered {
+                dao,
+                name,
+                metadata_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Point a listing at a new metadata document
+        #[ink(message)]
+        pub fn update_metadata(&mut self, dao: H160, metadata_hash: H256) -> Result<()> {
+            let mut entry = self.entries.get(dao).ok_or(Error::NotRegistered)?;
+            self.ensure_authorized(dao)?;
+            entry.metadata_hash = metadata_hash;
+            self.entries.insert(dao, &entry);
+
+            Self::env().emit_event(DaoMetadataUpdated { dao, metadata_hash });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_entry(&self, dao: H160) -> Option<DaoEntry> {
+            self.entries.get(dao)
+        }
+
+        #[ink(message)]
+        pub fn get_by_name(&self, name: String) -> Option<DaoEntry> {
+            self.names.get(&name).and_then(|dao| self.entries.get(dao))
+        }
+
+        #[ink(message)]
+        pub fn get_dao_count(&self) -> u32 {
+            self.dao_count
+        }
+
+        /// Up to `limit` listings in registration order from index `start`, at most
+        /// `MAX_PAGE_SIZE` per call
+        #[ink(message)]
+        pub fn get_entries(&self, start: u32, limit: u32) -> Vec<DaoEntry> {
+            let end = start
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(self.dao_count);
+            (start..end)
+                .filter_map(|index| self.registered.get(index))
+                .filter_map(|dao| self.entries.get(dao))
+                .collect()
+        }
+
+        /// The caller is the DAO or, asked through a cross-contract call, one of its members
+        fn ensure_authorized(&self, dao: H160) -> Result<()> {
+            let caller = self.env().caller();
+            if caller == dao {
+                return Ok(());
+            }
+            let dao_ref: ink::contract_ref_from_path!(Dao) = dao.into();
+            if !dao_ref.is_member(caller) {
+                return Err(Error::NotAuthorized);
+            }
+            Ok(())
+        }
+    }
+}
+
+pub use self::dao_registry::*;
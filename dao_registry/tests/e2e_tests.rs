@@ -0,0 +1,96 @@
+//! End-to-end tests of member authorization against a deployed `SimpleDao` on a node
+//! running `pallet-revive`, e.g. `ink-node` on the PATH or at `CONTRACTS_NODE`. Run with
+//! `cargo test -p dao_registry --features e2e-tests --test e2e_tests`.
+#![cfg(feature = "e2e-tests")]
+
+use dao_registry::*;
+use ink::env::DefaultEnvironment;
+use ink::H256;
+use ink_e2e::{ContractsBackend, Sr25519Keyring};
+use simple_dao::SimpleDaoRef;
+
+type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+// Helper function to get the address of a dev account
+fn address(account: Sr25519Keyring) -> ink::H160 {
+    ink_e2e::address::<DefaultEnvironment>(account)
+}
+
+#[ink_e2e::test]
+async fn members_register_and_outsiders_are_refused(
+    mut client: ink_e2e::Client<C, E>,
+) -> E2EResult<()> {
+    // given
+    let mut dao_constructor = SimpleDaoRef::new(
+        vec![address(Sr25519Keyring::Alice), address(Sr25519Keyring::Bob)],
+        2000,
+        10, // voting period
+        1,  // min votes required
+    );
+    let dao = client
+        .instantiate("simple_dao", &ink_e2e::alice(), &mut dao_constructor)
+        .submit()
+        .await
+        .expect("dao instantiate failed")
+        .addr;
+    let mut constructor = DaoRegistryRef::new();
+    let contract = client
+        .instantiate("dao_registry", &ink_e2e::alice(), &mut constructor)
+        .submit()
+        .await
+        .expect("registry instantiate failed");
+    let mut registry = contract.call_builder::<DaoRegistry>();
+
+    // when
+    let outsider = client
+        .call(
+            &ink_e2e::charlie(),
+            &registry.register(dao, "Bohemia".to_string(), H256::zero()),
+        )
+        .dry_run()
+        .await?
+        .return_value();
+    client
+        .call(
+            &ink_e2e::bob(),
+            &registry.register(dao, "Bohemia".to_string(), H256::zero()),
+        )
+        .submit()
+        .await
+        .expect("register failed")
+        .return_value()
+        .expect("member refused");
+
+    // then
+    assert_eq!(outsider, Err(Error::NotAuthorized));
+    let entry = client
+        .call(&ink_e2e::alice(), &registry.get_entry(dao))
+        .dry_run()
+        .await?
+        .return_value()
+        .expect("dao not listed");
+    assert_eq!(entry.registrant, address(Sr25519Keyring::Bob));
+
+    // Any member may update the listing, outsiders may not
+    let update = client
+        .call(
+            &ink_e2e::charlie(),
+            &registry.update_metadata(dao, H256::from([8; 32])),
+        )
+        .dry_run()
+        .await?
+        .return_value();
+    assert_eq!(update, Err(Error::NotAuthorized));
+    client
+        .call(
+            &ink_e2e::alice(),
+            &registry.update_metadata(dao, H256::from([8; 32])),
+        )
+        .submit()
+        .await
+        .expect("update_metadata failed")
+        .return_value()
+        .expect("member refused");
+
+    Ok(())
+}
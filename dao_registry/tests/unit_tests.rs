@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use dao_registry::*;
+    use ink::env::test;
+    use ink::{H160, H256};
+
+    #[ink::test]
+    fn test_dao_registers_itself() {
+        let dao = H160::from([1; 20]);
+        test::set_caller(dao);
+
+        let mut registry = DaoRegistry::new();
+        registry
+            .register(dao, "Bohemia".to_string(), H256::from([7; 32]))
+            .unwrap();
+
+        let entry = registry.get_by_name("Bohemia".to_string()).unwrap();
+        assert_eq!(entry.dao, dao);
+        assert_eq!(entry.registrant, dao);
+        assert_eq!(entry.metadata_hash, H256::from([7; 32]));
+        assert_eq!(registry.get_entry(dao), Some(entry.clone()));
+        assert_eq!(registry.get_dao_count(), 1);
+        assert_eq!(registry.get_entries(0, 10), vec![entry]);
+
+        registry.update_metadata(dao, H256::from([8; 32])).unwrap();
+        assert_eq!(
+            registry.get_entry(dao).unwrap().metadata_hash,
+            H256::from([8; 32])
+        );
+    }
+
+    #[ink::test]
+    fn test_register_rejects_duplicates_and_bad_names() {
+        let dao = H160::from([1; 20]);
+        let other_dao = H160::from([2; 20]);
+        test::set_caller(dao);
+
+        let mut registry = DaoRegistry::new();
+        assert_eq!(
+            registry.register(dao, String::new(), H256::zero()),
+            Err(Error::InvalidName)
+        );
+        assert_eq!(
+            registry.register(dao, "x".repeat(65), H256::zero()),
+            Err(Error::InvalidName)
+        );
+        registry
+            .register(dao, "Bohemia".to_string(), H256::zero())
+            .unwrap();
+        assert_eq!(
+            registry.register(dao, "Moravia".to_string(), H256::zero()),
+            Err(Error::AlreadyRegistered)
+        );
+
+        test::set_caller(other_dao);
+        assert_eq!(
+            registry.register(other_dao, "Bohemia".to_string(), H256::zero()),
+            Err(Error::NameTaken)
+        );
+        test::set_caller(H160::from([3; 20]));
+        assert_eq!(
+            registry.update_metadata(H160::from([3; 20]), H256::zero()),
+            Err(Error::NotRegistered)
+        );
+    }
+
+    #[ink::test]
+    fn test_entries_are_paged_in_registration_order() {
+        let mut registry = DaoRegistry::new();
+        for n in 1..=5u8 {
+            let dao = H160::from([n; 20]);
+            test::set_caller(dao);
+            registry
+                .register(dao, format!("DAO {n}"), H256::zero())
+                .unwrap();
+        }
+
+        let names = |entries: Vec<DaoEntry>| -> Vec<String> {
+            entries.into_iter().map(|entry| entry.name).collect()
+        };
+        assert_eq!(registry.get_dao_count(), 5);
+        assert_eq!(names(registry.get_entries(0, 2)), vec!["DAO 1", "DAO 2"]);
+        assert_eq!(
+            names(registry.get_entries(2, 10)),
+            vec!["DAO 3", "DAO 4", "DAO 5"]
+        );
+        assert!(registry.get_entries(5, 10).is_empty());
+        assert!(registry.get_entries(u32::MAX, u32::MAX).is_empty());
+    }
+}
@@ -1,362 +1,10403 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+pub mod sortition;
+#[cfg(feature = "std")]
+pub mod testing;
+
 #[ink::contract]
 mod simple_dao {
+    use ink::codegen::TraitCallBuilder;
+    use ink::env::hash::Keccak256;
+    use ink::prelude::boxed::Box;
     use ink::prelude::string::String;
     use ink::prelude::vec;
     use ink::prelude::vec::Vec;
-    use ink::storage::Mapping;
-    use ink::H160;
+    use ink::scale::DecodeLimit;
+    use ink::storage::{Lazy, Mapping};
+    use ink::xcm::{VersionedLocation, VersionedXcm, MAX_XCM_DECODE_DEPTH};
+    use ink::{H160, H256, U256};
 
-    #[derive(Debug, PartialEq)]
+    use crate::sortition::{self, RandomnessOracle};
+
+    /// Longest display name accepted in a member profile, in bytes
+    const MAX_PROFILE_NAME_LEN: usize = 64;
+    /// Longest avatar URI accepted in a member profile, in bytes
+    const MAX_AVATAR_URI_LEN: usize = 256;
+    /// Longest DAO description accepted in its metadata, in bytes
+    const MAX_DAO_DESCRIPTION_LEN: usize = 1_024;
+    /// Longest `dao_uri` accepted in the DAO metadata, in bytes
+    const MAX_DAO_URI_LEN: usize = 256;
+    /// Longest proposal name, in bytes
+    const MAX_PROPOSAL_NAME_LEN: usize = 128;
+    /// Longest proposal description, in bytes
+    const MAX_PROPOSAL_DESCRIPTION_LEN: usize = 4_096;
+    /// Longest label of a single voting option, in bytes
+    const MAX_OPTION_LEN: usize = 64;
+    /// Most voting options a proposal may offer
+    const MAX_OPTIONS: usize = 16;
+    /// Most comments a single proposal takes
+    const MAX_COMMENTS_PER_PROPOSAL: u32 = 256;
+    /// Most accounts subscribed to a single topic, each delivery writes to all of their queues
+    const MAX_SUBSCRIBERS_PER_TOPIC: u32 = 32;
+    /// Unread notifications kept per subscriber, older ones are dropped first
+    const MAX_UNREAD_NOTIFICATIONS: u32 = 64;
+    /// Domain tag signed into every meta-transaction, versioned with the payload layout
+    const META_TX_DOMAIN: &[u8] = b"BohemiaDao/meta-tx/v1";
+    /// Steepest growth of the rate limit surcharge, each step at most 11x the previous
+    const MAX_SURCHARGE_GROWTH_PERCENT: u32 = 1_000;
+    /// Largest number of entries returned by a paginated view
+    const MAX_PAGE_SIZE: u32 = 100;
+    /// Donors kept on the leaderboard, deposits only ever re-rank this many
+    const MAX_TOP_DONORS: usize = 32;
+    /// Upper bound for the member count, governance may only lower it
+    const MAX_MEMBERS: u32 = 1_000;
+    /// Reputation earned for casting a vote
+    const REPUTATION_PER_VOTE: u32 = 1;
+    /// Reputation earned by the author of a passed proposal
+    const REPUTATION_PER_PASSED_PROPOSAL: u32 = 5;
+    /// Votes on closed proposals that earn the `Voter` badge
+    const BADGE_VOTES: u32 = 10;
+    /// Closed proposals in a row voted on that earn the `TurnoutStreak` badge
+    const BADGE_STREAK: u32 = 10;
+    /// Reputation earned by the recipient of a released escrow milestone
+    const REPUTATION_PER_MILESTONE: u32 = 3;
+    /// Most reputation a member earns per voting period, so it can't be farmed with
+    /// a flood of proposals
+    const MAX_REPUTATION_PER_EPOCH: u32 = 20;
+    /// Fixed-point scale of the cumulative dividend per token
+    const DIVIDEND_PRECISION: u128 = 1_000_000_000_000;
+    /// Blocks a passed upgrade waits before it can run, about a day of 6s blocks
+    const DEFAULT_UPGRADE_TIMELOCK: u64 = 14_400;
+    /// Blocks unstaked tokens stay locked by default, about a week of 6s blocks, so they
+    /// can't be restaked by another account while a vote on them runs
+    const DEFAULT_UNBONDING_PERIOD: u64 = 100_800;
+    /// Largest emergency council governance may elect
+    const MAX_COUNCIL_SIZE: u32 = 9;
+    /// Most listener contracts notified of governance events
+    const MAX_HOOKS: u32 = 8;
+    /// Largest ref-time a single hook call may use
+    const MAX_HOOK_REF_TIME: u64 = 10_000_000_000;
+    /// Longest voting period governance may set, about four weeks of 6s blocks
+    const MAX_VOTING_PERIOD: u64 = 403_200;
+    /// Most hops a delegation chain may take to reach the account voting with the stake
+    const MAX_DELEGATION_HOPS: usize = 4;
+
+    #[derive(Debug, Clone, PartialEq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
     pub enum ProposalType {
         MultipleChoice,
         MoneyRequest,
+        RegisterAsset(H160), // Adds a PSP22 token to the treasury asset registry
+        CancelStream(u32),   // Stops a payment stream and returns unaccrued funds
+        CancelEscrow(u32),   // Refunds all unreleased milestones of an escrow
+        RecurringPayment {
+            recipient: H160,
+            asset: Option<H160>,
+            amount: Balance,
+            interval: u64, // in blocks
+            end_block: u64,
+        },
+        CancelRecurringPayment(u32),
+        SetProposalDeposit(Balance),
+        AllocateBudget {
+            envelope: String,
+            asset: Option<H160>,
+            amount: Balance,
+        },
+        SetMaxRequestPercent(Option<u8>), // `None` removes the cap
+        ConfigureTips {
+            cap: Balance,    // Largest tip amount, 0 disables tipping
+            approvals: u32,  // At least two, so no single member can pay out a tip
+            budget: Balance, // Total paid out in tips per period
+            period: u64,     // Blocks per budget period
+        },
+        GrantProgress(u32), // Options: release next tranche / claw back the rest
+        ConfigureVoteRebates {
+            rate: Balance, // Estimated cost of casting a vote, 0 disables rebates
+            cap_per_proposal: Balance,
+        },
+        AddMember(H160), // Invites the account; proposal amount, if any, is minted on acceptance
+        RemoveMember(H160), // Handles the member's tokens according to the kick policy
+        SetKickPolicy(KickPolicy),
+        AssignTier {
+            account: H160,
+            tier: Tier,
+        },
+        AddObserver(H160),
+        RemoveObserver(H160),
+        SetAllowlisted {
+            account: H160, // May self-join with `join`
+            allowed: bool,
+        },
+        SetJoinFee(Balance),
+        ConfigureBuyIn {
+            tokens: Balance, // Tokens issued for every `price` of native value, 0 disables buy-ins
+            price: Balance,
+            period: u64,  // Blocks per issuance period
+            cap: Balance, // Tokens issued through buy-ins per period
+        },
+        SlashReputation {
+            member: H160,
+            amount: u32,
+        },
+        SetVoteWeightSource(VoteWeightSource),
+        Slash {
+            member: H160,
+            amount: Balance, // Capped at the member's balance, staked tokens included
+            reason_hash: [u8; 32], // Hash of the off-chain justification
+            to_treasury: bool, // Confiscate into the DAO's token pool instead of burning
+        },
+        CharterCommittee {
+            name: String,
+            members: Vec<H160>,
+            envelope: String,     // Budget envelope the committee spends from
+            quorum: u32,          // Committee approvals needed for a spend
+            max_request: Balance, // Largest single spend the committee may approve
+        },
+        DissolveCommittee(u32),
+        SetMaxMembers(u32),
+        SetResignationPolicy(ResignationPolicy),
+        SetMemberOnlyTransfers(bool), // Restrict token transfers to member recipients
+        TokenMint {
+            recipient: H160,
+            amount: Balance,
+        },
+        ConfigureEmission {
+            amount: Balance, // Tokens minted to the DAO's token pool per epoch, 0 stops emission
+            epoch_length: u64, // Blocks per epoch
+            decay_percent: u8, // Reduction of the amount after every epoch, 0 keeps it fixed
+        },
+        AwardTokens {
+            recipient: H160, // Paid out of the DAO's token pool
+            amount: Balance,
+        },
+        SetAirdropRoot([u8; 32]), // Opens a new airdrop round, an all-zero root closes airdrops
+        SetWrappedToken(Option<H160>), // External PSP22 wrapped 1:1 into governance tokens
+        Buyback {
+            market: H160,        // `TokenMarket` selling the tokens
+            payment: Balance,    // Native treasury funds paid to the market
+            min_tokens: Balance, // Slippage limit, fewer quoted tokens abort the buyback
+        },
+        GrantVesting {
+            recipient: H160,
+            amount: Balance,
+            cliff: u64,
+            duration: u64,
+        },
+        SetVestingVotePower(bool), // Count vested but unclaimed tokens as voting power
+        SetUnbondingPeriod(u64),   // Blocks unstaked tokens stay locked
+        ConfigureTier {
+            tier: Tier,
+            vote_weight: u32, // Votes counted per ballot of a tier member
+            can_propose: bool,
+        },
+        AssignRole {
+            account: H160,
+            role: Role,
+        },
+        ConfigureMembershipTerm {
+            term: u64, // Blocks a membership lasts without renewal, 0 disables expiry
+            renewal_fee: Balance,
+        },
+        ConfigChange(ConfigUpdate),
+        Upgrade(H256), // Replace the contract code, keeping storage and treasury
+        SetUpgradeTimelock(u64), // Blocks between passing and executing a sensitive action
+        SetPauseQuorum(u32), // Members whose `pause` calls pause the DAO, 0 leaves it to guardians
+        Unpause,       // The only proposal created, voted on and executed while paused
+        ElectCouncil(Vec<H160>), // Replaces the emergency council, an empty list dissolves it
+        ConfigureProposalType {
+            kind: u8,                   // Variant index, see `proposal_kind`
+            config: Option<TypeConfig>, // `None` restores the global rules
+        },
+        XcmExecute(Vec<u8>), // SCALE-encoded `VersionedXcm<()>` run locally, e.g. a reserve transfer
+        XcmSend {
+            dest: Vec<u8>,    // SCALE-encoded `VersionedLocation`
+            message: Vec<u8>, // SCALE-encoded `VersionedXcm<()>`
+        },
+        SetRandomnessOracle(Option<H160>), // Contract implementing `RandomnessOracle`
+        DrawCommittee {
+            name: String,
+            size: u32, // Members drawn by lot once the randomness arrives
+            envelope: String,
+            quorum: u32,
+            max_request: Balance,
+        },
+        Lottery {
+            prize: Balance, // Native treasury funds paid to one member drawn by lot
+        },
+        SetExecutor(Option<H160>), // Contract that alone executes passed proposals, e.g. a multisig
+        RegisterHook {
+            contract: H160,      // Implements `GovernanceHook`
+            ref_time_limit: u64, // Gas bound of each notification
+        },
+        UnregisterHook(H160),
+        SetDaoMetadata(DaoMetadata),
+        SetRateLimit(RateLimit), // Escalates deposits of authors proposing a lot within an epoch
+        FreezeMember(H160),      // Locks out a member whose key is believed compromised
+        UnfreezeMember {
+            member: H160,
+            migrate_to: Option<H160>, // Offer the membership to a new address instead of unfreezing
+        },
+        Dispute(u32), // Contests a proposal in its timelock, passing it overturns the proposal
+        ConfigureDisputes {
+            bond: Balance,         // Deposit of a dispute, 0 disables disputes
+            arbiter: Option<H160>, // Decides disputes instead of a member vote
+        },
+        ConfigureJury {
+            kind: u8,  // Variant index, see `proposal_kind`
+            size: u32, // Members drawn to vote on each proposal of the kind, 0 lets everyone vote
+        },
+        ConfigureVoteDecay {
+            kind: u8,
+            bonus_percent: u8, // Extra weight of a vote cast when voting opens, 0 disables decay
+        },
+        Election {
+            seats: u32, // Options with the most votes that win, decided once voting ends
+        },
+        ConfigureCouncilTerms {
+            term: u64,  // Blocks an elected council sits, 0 stops recurring elections
+            seats: u32, // Council seats filled by each election
+        },
+        BudgetVote {
+            amounts: Vec<Option<Balance>>, // Native funds paid to the author per option, `None` pays nothing
+        },
+        ConfigureMembership {
+            endorsements: u32,       // Endorsements (or rejections) that decide an application
+            initial_tokens: Balance, // Minted to approved applicants
+        },
     }
 
-    #[derive(Debug, PartialEq, Eq)]
+    /// Optional parameters of a money request
+    #[derive(Debug, Default, Clone, PartialEq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
-    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
-    pub enum ProposalStatus {
-        Active,
-        Passed,
-        Rejected,
-        Expired,
+    pub struct MoneyRequestTerms {
+        pub asset: Option<H160>, // Registered PSP22 token, `None` for native balance
+        pub stream_duration: Option<u64>, // Release linearly over this many blocks
+        pub milestones: Vec<Balance>, // Escrowed tranches, must sum up to the amount
+        pub envelope: Option<String>, // Budget envelope the request is drawn from
+        pub tranches: Vec<Balance>, // Grant tranches, later ones need progress votes
+        pub recipient: Option<H160>, // Payee, the author if `None`
     }
 
-    #[derive(Debug)]
+    #[derive(Debug, PartialEq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
-    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
-    pub struct Proposal {
-        pub id: u32,
-        pub name: String,
-        pub description: String,
-        pub author: H160,
-        pub proposal_type: ProposalType,
-        pub options: Vec<String>, // For multiple choice or single option for money request
-        pub amount: Option<Balance>, // For money request proposals
-        pub votes: Vec<u32>,      // Vote count for each option
-        pub voted_members: Vec<H160>,
-        pub status: ProposalStatus,
-        pub created_at: u64,
-        pub voting_deadline: u64,
+    pub enum PSP22Error {
+        Custom(String),
+        InsufficientBalance,
+        InsufficientAllowance,
+        ZeroRecipientAddress,
+        ZeroSenderAddress,
+        SafeTransferCheckFailed(String),
     }
 
-    #[ink(storage)]
-    pub struct SimpleDao {
-        members: Vec<H160>,
-        member_tokens: Mapping<H160, Balance>,
-        total_supply: Balance,
+    /// Token id of the PSP34 standard
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Id {
+        U8(u8),
+        U16(u16),
+        U32(u32),
+        U64(u64),
+        U128(u128),
+        Bytes(Vec<u8>),
+    }
 
-        proposals: Mapping<u32, Proposal>,
-        next_proposal_id: u32,
+    #[derive(Debug, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum PSP34Error {
+        Custom(String),
+        SelfApprove,
+        NotApproved,
+        TokenExists,
+        TokenNotExists,
+        SafeTransferCheckFailed(String),
+    }
 
-        voting_period: u64, // in blocks
-        min_votes_required: u32,
+    /// Subset of the PSP22 standard used by the treasury
+    #[ink::trait_definition]
+    pub trait PSP22 {
+        #[ink(message, selector = 0x162df8c2)]
+        fn total_supply(&self) -> Balance;
+
+        #[ink(message, selector = 0x6568382f)]
+        fn balance_of(&self, owner: H160) -> Balance;
+
+        #[ink(message, selector = 0x4d47d921)]
+        fn allowance(&self, owner: H160, spender: H160) -> Balance;
+
+        #[ink(message, selector = 0xb20f1bbd)]
+        fn approve(
+            &mut self,
+            spender: H160,
+            value: Balance,
+        ) -> core::result::Result<(), PSP22Error>;
+
+        #[ink(message, selector = 0xdb20f9f5)]
+        fn transfer(
+            &mut self,
+            to: H160,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> core::result::Result<(), PSP22Error>;
+
+        #[ink(message, selector = 0x54b3c76e)]
+        fn transfer_from(
+            &mut self,
+            from: H160,
+            to: H160,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> core::result::Result<(), PSP22Error>;
     }
 
-    #[derive(Debug)]
-    #[ink(event)]
-    pub struct DaoInitiated {
-        #[ink(topic)]
-        members: Vec<H160>,
-        total_supply: Balance,
+    /// Seller of governance tokens used by buybacks. The market approves the DAO
+    /// to take the quoted tokens from its governance token balance.
+    #[ink::trait_definition]
+    pub trait TokenMarket {
+        /// Governance tokens the market sells for `payment` native value
+        #[ink(message)]
+        fn quote(&self, payment: Balance) -> Balance;
     }
 
-    #[derive(Debug)]
-    #[ink(event)]
-    pub struct ProposalCreated {
-        #[ink(topic)]
-        proposal_id: u32,
-        #[ink(topic)]
-        author: H160,
-        name: String,
+    /// Governance event delivered to registered hooks and subscriber queues
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum HookEvent {
+        ProposalPassed(u32),
+        ProposalExecuted(u32),
+        MemberJoined(H160),
+        MemberLeft(H160),
+        MembershipTransferred { from: H160, to: H160 },
     }
 
-    #[derive(Debug)]
-    #[ink(event)]
-    pub struct VoteCast {
-        #[ink(topic)]
-        proposal_id: u32,
-        #[ink(topic)]
-        voter: H160,
-        option: u32,
+    /// Listener contract extending the DAO, e.g. a reward distributor
+    #[ink::trait_definition]
+    pub trait GovernanceHook {
+        #[ink(message)]
+        fn on_governance_event(&mut self, event: HookEvent);
     }
 
-    #[derive(Debug)]
-    #[ink(event)]
-    pub struct TokensDistributed {
-        #[ink(topic)]
-        recipient: H160,
-        amount: Balance,
+    /// What a watcher wants to hear about
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum Subscription {
+        Proposal(u32), // One proposal passing and executing
+        Proposals,     // Every proposal passing and executing
+        Membership,    // Members joining, leaving and transferring their membership
     }
 
-    // Custom errors
-    #[derive(Debug, PartialEq)]
+    /// Governance event waiting in a subscriber's queue
+    #[derive(Debug, Clone, PartialEq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
-    pub enum Error {
-        NotMember,
-        ProposalNotFound,
-        ProposalExpired,
-        AlreadyVoted,
-        InvalidOption,
-        InvalidProposalType,
-        InsufficientBalance,
-        EmptyMembers,
-        InvalidVotingPeriod,
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Notification {
+        pub event: HookEvent,
+        pub block: u64,
     }
 
-    pub type Result<T> = core::result::Result<T, Error>;
+    /// Listener registered through a `RegisterHook` proposal
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Hook {
+        pub contract: H160,
+        pub ref_time_limit: u64,
+    }
 
-    impl SimpleDao {
-        /// Initialize the DAO with founding members
-        #[ink(constructor)]
-        pub fn new(
-            members: Vec<H160>,
-            total_supply: Balance,
-            voting_period: u64,
-            min_votes_required: u32,
-        ) -> Self {
-            assert!(members.len() > 0, "Invalid number of members specified.");
-            assert!(voting_period > 0, "Invalid voting period.");
+    /// Token metadata of the PSP22 standard
+    #[ink::trait_definition]
+    pub trait PSP22Metadata {
+        #[ink(message, selector = 0x3d261bd4)]
+        fn token_name(&self) -> Option<String>;
 
-            let mut dao = Self {
-                members: members.clone(),
-                member_tokens: Mapping::new(),
-                total_supply,
-                proposals: Mapping::new(),
-                next_proposal_id: 1,
-                voting_period,
-                min_votes_required,
-            };
+        #[ink(message, selector = 0x34205be5)]
+        fn token_symbol(&self) -> Option<String>;
 
-            // Distribute initial tokens equally among founding members
-            let tokens_per_member = total_supply / members.len() as Balance;
-            for member in &members {
-                dao.member_tokens.insert(member, &tokens_per_member);
-            }
+        #[ink(message, selector = 0x7271b782)]
+        fn token_decimals(&self) -> u8;
+    }
 
-            Self::env().emit_event(DaoInitiated {
-                members,
-                total_supply,
-            });
+    /// The PSP34 NFT standard
+    #[ink::trait_definition]
+    pub trait PSP34 {
+        #[ink(message, selector = 0xffa27a5f)]
+        fn collection_id(&self) -> Id;
 
-            dao
-        }
+        #[ink(message, selector = 0xcde7e55f)]
+        fn balance_of(&self, owner: H160) -> u32;
 
-        /// Distribute additional tokens to a member (only callable by contract)
-        #[ink(message)]
-        pub fn distribute_tokens(&mut self, recipient: H160, amount: Balance) -> Result<()> {
-            // Only existing members can receive tokens
-            if !self.members.contains(&recipient) {
-                return Err(Error::NotMember);
-            }
+        #[ink(message, selector = 0x1168624d)]
+        fn owner_of(&self, id: Id) -> Option<H160>;
 
-            let current_balance = self.member_tokens.get(&recipient).unwrap_or(0);
-            self.member_tokens
-                .insert(&recipient, &(current_balance + amount));
-            self.total_supply += amount;
+        #[ink(message, selector = 0x4790f55a)]
+        fn allowance(&self, owner: H160, operator: H160, id: Option<Id>) -> bool;
 
-            Self::env().emit_event(TokensDistributed { recipient, amount });
+        #[ink(message, selector = 0x1932a8b0)]
+        fn approve(
+            &mut self,
+            operator: H160,
+            id: Option<Id>,
+            approved: bool,
+        ) -> core::result::Result<(), PSP34Error>;
 
-            Ok(())
-        }
+        #[ink(message, selector = 0x3128d61b)]
+        fn transfer(
+            &mut self,
+            to: H160,
+            id: Id,
+            data: Vec<u8>,
+        ) -> core::result::Result<(), PSP34Error>;
 
-        /// Create a new proposal
-        #[ink(message)]
-        pub fn create_proposal(
+        #[ink(message, selector = 0x628413fe)]
+        fn total_supply(&self) -> Balance;
+    }
+
+    /// Core governance surface of a DAO. Other contracts can drive any compliant DAO through
+    /// `contract_ref!(Dao)`.
+    #[ink::trait_definition]
+    pub trait Dao {
+        /// Create a new proposal, locking the deposit its proposal type requires
+        #[ink(message, payable, selector = 0xf9fb13d3)]
+        fn create_proposal(
             &mut self,
             name: String,
             description: String,
             proposal_type: ProposalType,
             options: Vec<String>,
             amount: Option<Balance>,
-        ) -> Result<u32> {
-            let caller: H160 = self.env().caller();
-
-            // Validate proposal based on type
-            match proposal_type {
-                ProposalType::MultipleChoice => {
-                    if options.is_empty() {
-                        return Err(Error::InvalidProposalType);
-                    }
-                }
-                ProposalType::MoneyRequest => {
-                    if amount.is_none() || options.len() != 1 {
-                        return Err(Error::InvalidProposalType);
-                    }
-                }
-            }
+        ) -> Result<u32>;
 
-            let proposal_id = self.next_proposal_id;
-            let current_block = self.env().block_number() as u64;
+        /// Cast a vote on a proposal
+        #[ink(message, selector = 0x083be260)]
+        fn vote(&mut self, proposal_id: u32, option: u32) -> Result<()>;
 
-            let proposal = Proposal {
-                id: proposal_id,
-                name: name.clone(),
-                description,
-                author: caller,
-                proposal_type,
-                options: options.clone(),
-                amount,
-                votes: vec![0; options.len()],
-                voted_members: Vec::new(),
-                status: ProposalStatus::Active,
-                created_at: current_block,
-                voting_deadline: current_block as u64 + self.voting_period,
-            };
+        /// Execute a passed proposal
+        #[ink(message, selector = 0xde5bbbbc)]
+        fn execute_proposal(&mut self, proposal_id: u32) -> Result<()>;
 
-            self.proposals.insert(&proposal_id, &proposal);
-            self.next_proposal_id += 1;
+        /// Close the voting of an expired proposal and settle its deposit (callable by anyone)
+        #[ink(message, selector = 0x745e9f7a)]
+        fn finalize_proposal(&mut self, proposal_id: u32) -> Result<()>;
 
-            Self::env().emit_event(ProposalCreated {
-                proposal_id,
-                author: caller,
-                name,
-            });
+        #[ink(message, selector = 0xba4dc5ec)]
+        fn get_proposal(&self, proposal_id: u32) -> Result<Proposal>;
 
-            Ok(proposal_id)
-        }
+        #[ink(message, selector = 0x38539121)]
+        fn get_active_proposals(&self) -> Vec<u32>;
 
-        /// Cast a vote on a proposal
-        #[ink(message)]
-        pub fn vote(&mut self, proposal_id: u32, option: u32) -> Result<()> {
-            let caller: H160 = self.env().caller();
+        /// Live vote count, decided with the same rules the next vote applies
+        #[ink(message, selector = 0x01d0e109)]
+        fn get_tally(&self, proposal_id: u32) -> Result<Tally>;
 
-            // Check if caller is a member
-            if !self.members.contains(&caller) {
-                return Err(Error::NotMember);
-            }
+        /// Weight a vote of `member` counts with, whatever the configured weight source.
+        /// With a proposal, 0 unless the member could vote on it right now.
+        #[ink(message, selector = 0x47f5deed)]
+        fn voting_power_of(&self, member: H160, proposal_id: Option<u32>) -> u32;
 
-            let mut proposal = self
-                .proposals
-                .get(&proposal_id)
-                .ok_or(Error::ProposalNotFound)?;
+        #[ink(message, selector = 0x4efcf717)]
+        fn is_member(&self, account: H160) -> bool;
 
-            // Check if member has altready voted
-            if proposal.voted_members.contains(&caller) {
-                return Err(Error::AlreadyVoted);
-            }
+        /// All governance parameters in one call
+        #[ink(message, selector = 0xd0707806)]
+        fn get_config(&self) -> DaoConfig;
+    }
 
-            // Check if proposal is still active
-            if proposal.status != ProposalStatus::Active {
-                return Err(Error::ProposalExpired);
-            }
+    /// Checkpointed voting power of a votes-style governance token
+    #[ink::trait_definition]
+    pub trait PSP22Votes {
+        /// Votes of `account` at the end of `block`
+        #[ink(message)]
+        fn get_past_votes(&self, account: H160, block: u64) -> Balance;
+    }
 
-            // Check if voting period has expired
-            if self.env().block_number() as u64 > proposal.voting_deadline {
-                proposal.status = ProposalStatus::Expired;
-                self.proposals.insert(&proposal_id, &proposal);
-                return Err(Error::ProposalExpired);
-            }
+    /// Token contract members' voting power is read from instead of the internal ledger
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct ExternalToken {
+        pub token: H160,
+        pub votes_style: bool, // Implements `PSP22Votes`, required to weigh votes with it
+        pub tokens_per_vote: Balance, // Token units making up one vote, at least 1
+    }
 
-            // Validate option
-            if option as usize >= proposal.options.len() {
-                return Err(Error::InvalidOption);
-            }
+    /// Construction-time settings of the governance token
+    #[derive(Debug, Default, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct TokenConfig {
+        pub name: Option<String>,
+        pub symbol: Option<String>,
+        pub decimals: u8,
+        pub soulbound: bool, // Tokens can never be transferred or approved
+        pub external: Option<ExternalToken>, // Vote with this token's balances instead
+    }
 
-            // Cast vote
-            proposal.votes[option as usize] += 1;
-            proposal.voted_members.push(caller);
+    /// Emergency council action needing a council majority
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum CouncilAction {
+        Veto,      // Stop a passed proposal from being executed
+        FastTrack, // Close voting on a security proposal and skip its timelock
+    }
 
-            // Update proposal status if needed
-            self.update_proposal_status(&mut proposal);
+    /// Core voting parameter set by a `ConfigChange` proposal
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum ConfigUpdate {
+        VotingPeriod(u64),     // Applies to proposals created afterwards
+        MinVotesRequired(u32), // At most the current member count
+        PassThreshold(u8),     // Percent of the votes the winning option must exceed, 50 to 99
+        ChainId(u64), // EIP-155 id signed into meta-transactions, meta-transactions are off while 0
+        RunoffPeriod(u64), // Blocks a runoff between the two leading options runs, 0 disables runoffs
+        QuorumMode(QuorumMode),
+    }
 
-            self.proposals.insert(&proposal_id, &proposal);
+    /// How the quorum of a proposal is measured
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum QuorumMode {
+        #[default]
+        Votes, // `min_votes_required` of the proposal's kind, counted in weighted votes
+        PowerPercent(u8), // Percent of the voting power at proposal creation, 1 to 100
+    }
 
-            Self::env().emit_event(VoteCast {
-                proposal_id,
-                voter: caller,
-                option,
-            });
+    /// Voting rules of one kind of proposal, set by a `ConfigureProposalType` proposal
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct TypeConfig {
+        pub min_votes_required: u32, // Quorum
+        pub pass_threshold: u8,      // Percent of the votes the winning option must exceed
+        pub voting_period: u64,
+        pub deposit: Balance,
+        pub timelock: u64, // Sensitive actions still wait at least the upgrade timelock
+    }
 
-            Ok(())
-        }
+    /// Per-author proposal allowance, set by a `SetRateLimit` proposal. Past the free
+    /// proposals of an epoch every proposal adds a surcharge to the deposit, growing
+    /// geometrically until the epoch ends.
+    #[derive(Debug, Default, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct RateLimit {
+        pub epoch_length: u64, // Blocks per counting window, 0 turns rate limiting off
+        pub free_proposals: u32, // Proposals per author and epoch at the normal deposit
+        pub surcharge: Balance, // Extra deposit of the first proposal past the allowance
+        pub growth_percent: u32, // Each further surcharge exceeds the previous by this percent
+    }
 
-        #[ink(message)]
-        pub fn get_proposal(&self, proposal_id: u32) -> Result<Proposal> {
-            let mut proposal = self
-                .proposals
-                .get(&proposal_id)
-                .ok_or(Error::ProposalNotFound)?;
+    /// Governance parameters of the DAO, returned by `get_config`
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct DaoConfig {
+        pub voting_period: u64,
+        pub min_votes_required: u32, // Quorum
+        pub pass_threshold: u8,      // Percent of the votes the winning option must exceed
+        pub proposal_deposit: Balance,
+        pub max_request_percent: Option<u8>,
+        pub tip_cap: Balance,
+        pub tip_approvals_required: u32,
+        pub tip_budget: Balance,
+        pub tip_period: u64,
+        pub vote_rebate: Balance,
+        pub max_rebate_per_proposal: Balance,
+        pub membership_endorsements: u32,
+        pub membership_grant: Balance,
+        pub membership_term: u64,
+        pub renewal_fee: Balance,
+        pub join_fee: Balance,
+        pub max_members: u32,
+        pub kick_policy: KickPolicy,
+        pub resignation_policy: ResignationPolicy,
+        pub vote_weight_source: VoteWeightSource,
+        pub member_only_transfers: bool,
+        pub vested_vote_power: bool,
+        pub unbonding_period: u64,
+        pub buy_in_tokens: Balance,
+        pub buy_in_price: Balance,
+        pub buy_in_period: u64,
+        pub buy_in_cap: Balance,
+        pub upgrade_timelock: u64, // Delay of upgrades and other sensitive actions
+        pub pause_quorum: u32,
+        pub chain_id: u64, // Signed into meta-transactions, 0 until governance sets it
+        pub runoff_period: u64,
+        pub quorum_mode: QuorumMode,
+    }
 
-            // Update status if voting period expired
-            if proposal.status == ProposalStatus::Active
-                && self.env().block_number() as u64 > proposal.voting_deadline
-            {
-                proposal.status = ProposalStatus::Expired;
-            }
+    /// Native treasury holdings split by commitment, returned by `get_treasury`
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct TreasuryOverview {
+        pub balance: Balance,
+        pub reserved: Balance, // Streams, escrows, deposits and unclaimed payouts
+        pub streams: Balance,  // Part of `reserved` still owed to stream recipients
+        pub pending_requests: Balance, // Passed money requests not executed yet
+        pub spendable: Balance, // Left for new requests after all of the above
+        pub vesting_obligations: Balance, // Governance tokens granted but not yet claimed
+    }
 
-            Ok(proposal)
-        }
+    /// Current vote count of a proposal, returned by `get_tally`
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct Tally {
+        pub votes: Vec<u32>, // Weighted votes per option
+        pub total_votes: u32,
+        pub turnout: u32,           // Number of members who voted
+        pub quorum_percent: u8,     // Progress towards the quorum, capped at 100
+        pub blocks_remaining: u64,  // 0 once the voting deadline passed
+        pub status: ProposalStatus, // Status after applying the voting rules to this tally
+    }
+
+    /// Aggregate figures for dashboards, returned by `get_stats`
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct DaoStats {
+        pub total_proposals: u32,
+        pub draft_proposals: u32,
+        pub active_proposals: u32, // Includes expired ones awaiting `finalize_proposal`
+        pub passed_proposals: u32,
+        pub rejected_proposals: u32,
+        pub expired_proposals: u32,
+        pub cancelled_proposals: u32,
+        pub vetoed_proposals: u32,
+        pub average_turnout: Option<u8>, // Percent, `None` before any proposal closed
+        pub treasury_inflow: Balance,    // Native deposits, fees and buy-ins
+        pub treasury_outflow: Balance,   // Native payouts, rebates and buybacks
+        pub member_count: u32,
+        pub total_supply: Balance,
+    }
+
+    /// Permission level of a member, ordered from least to most privileged
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum Role {
+        #[default]
+        Member,
+        Moderator, // May flag spam proposals
+        Guardian,  // May pause the DAO
+        Admin,     // May fast-track proposals
+    }
+
+    /// Membership tier deciding vote weight and proposal rights
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum Tier {
+        Core,
+        #[default]
+        Contributor,
+        Supporter,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct TierConfig {
+        pub vote_weight: u32,
+        pub can_propose: bool,
+    }
+
+    impl Default for TierConfig {
+        fn default() -> Self {
+            Self {
+                vote_weight: 1,
+                can_propose: true,
+            }
+        }
+    }
+
+    /// Position of a member in the enumeration and when they joined
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct MemberInfo {
+        pub index: u32,
+        pub joined_at: u64,
+    }
+
+    /// Closed proposals a member could vote on and how many they voted on
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Participation {
+        pub eligible: u32,
+        pub voted: u32,
+    }
+
+    /// A member's votes as of their last settlement, ballots on proposals still open then
+    /// are booked once those close
+    #[derive(Debug, Default, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct VoteRecord {
+        pub joined_at: u32, // Closed proposals counted when the member joined
+        pub voted: u32,     // Closed proposals voted on
+        pub missed: u32,    // Closed proposals not voted on
+        pub streak: u32,    // Closed proposals voted on since the last miss
+        pub open: Vec<u32>, // Proposals voted on that were still open
+    }
+
+    /// Participation achievement, awarded when a member's votes are settled
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum Badge {
+        FirstProposal, // Authored a proposal that went to a decision
+        Voter,         // Voted on `BADGE_VOTES` closed proposals
+        TurnoutStreak, // Voted on `BADGE_STREAK` closed proposals in a row
+    }
+
+    /// Where the weight of a member's vote comes from
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum VoteWeightSource {
+        #[default]
+        Tier,
+        Reputation, // Reputation score, at least 1
+        Tokens,     // Stake voting through the member, optionally with unclaimed vested tokens
+        External,   // Balance in the external token set at construction
+        Blended {
+            reputation_percent: u8, // Share of the weight from reputation, the rest from tokens
+        },
+    }
+
+    /// Governance tokens unlocking linearly after a cliff
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct VestingSchedule {
+        pub total: Balance,
+        pub claimed: Balance,
+        pub start_block: u64,
+        pub cliff: u64,    // Blocks after start before anything unlocks
+        pub duration: u64, // Blocks after start until everything is unlocked
+    }
+
+    /// Token balance or voting power of an account from `block` on
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Checkpoint {
+        pub block: u64,
+        pub amount: Balance,
+    }
+
+    /// Stake reaching an account from members delegating to it, directly or down a chain
+    #[derive(Debug, Clone, Default, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct DelegatedStake {
+        pub stake: Balance,
+        pub depths: Vec<u32>, // Delegators by distance, index 0 one hop away, no trailing zeros
+    }
+
+    /// Tokens taken from a member by a passed `Slash` proposal
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct SlashRecord {
+        pub proposal_id: u32,
+        pub amount: Balance,
+        pub reason_hash: [u8; 32],
+        pub to_treasury: bool,
+        pub block: u64,
+    }
+
+    /// Unstaked tokens waiting out the unbonding period
+    #[derive(Debug, Default, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Unbonding {
+        pub amount: Balance,
+        pub release_block: u64,
+    }
+
+    /// What happens to the tokens of a member who resigns
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum ResignationPolicy {
+        #[default]
+        Burn,
+        Treasury, // Tokens are kept by the DAO itself
+    }
+
+    /// What happens to the tokens of a member removed by governance
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum KickPolicy {
+        #[default]
+        Burn,
+        Redistribute, // Split pro-rata among the remaining members
+        Escrow,       // Held aside and returned if the account rejoins
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum ProposalStatus {
+        Active,
+        Passed,
+        Rejected,
+        Expired,
+        Executed,
+        Cancelled, // Withdrawn by its author before anyone voted
+        Vetoed,    // Passed, then blocked by the emergency council
+        Draft,     // Created by an observer, waits for a member to sponsor it
+    }
+
+    #[derive(Debug)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Proposal {
+        pub id: u32,
+        pub name: String,
+        pub description: String, // Stored in `proposal_descriptions`, filled in by `get_proposal`
+        pub author: H160,
+        pub proposal_type: ProposalType,
+        pub options: Vec<String>, // For multiple choice or single option for money request
+        pub amount: Option<Balance>, // For money request proposals
+        pub asset: Option<H160>,  // PSP22 token of the money request, native if `None`
+        pub votes: Vec<u32>,      // Tier-weighted vote count for each option
+        pub voter_count: u32,     // Ballots are stored separately, see `get_vote`
+        pub status: ProposalStatus,
+        pub created_at: u64,
+        pub voting_deadline: u64,
+        pub stream_duration: Option<u64>, // Streamed payout length in blocks
+        pub milestones: Vec<Balance>,     // Escrowed payout tranches
+        pub envelope: Option<String>,     // Budget envelope charged on execution
+        pub tranches: Vec<Balance>,       // Grant tranches released by progress votes
+        pub recipient: Option<H160>,      // Payee of a money request, the author if `None`
+    }
+
+    /// What a proposal was created with. Its SCALE encoding is the canonical blob
+    /// `verify_proposal_content` checks against the hash stored at creation.
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct ProposalContent {
+        pub name: String,
+        pub description: String,
+        pub proposal_type: ProposalType,
+        pub options: Vec<String>,
+        pub amount: Option<Balance>,
+        pub terms: MoneyRequestTerms,
+    }
+
+    /// Second round between the two leading options of a `MultipleChoice` proposal
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Runoff {
+        pub proposal_id: u32,  // First round, expired without a majority
+        pub options: [u32; 2], // Options of the first round, in the runoff's option order
+    }
+
+    /// Winning options of a decided election
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct ElectionResult {
+        pub winners: Vec<u32>, // Option indices, most votes first
+        pub tie_broken: bool,  // The last seat went to the earlier of equally voted options
+    }
+
+    /// A seated emergency council, kept as history once replaced
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct CouncilTerm {
+        pub council: Vec<H160>,
+        pub proposal_id: Option<u32>, // `Election` or `ElectCouncil` that seated it, `None` if uncontested
+        pub seated_at: u64,
+        pub ends_at: Option<u64>, // `None` for a council governance elected directly
+    }
+
+    /// Compact proposal view for listings, `get_proposal` has the details
+    #[derive(Debug, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct ProposalSummary {
+        pub id: u32,
+        pub name: String,
+        pub status: ProposalStatus,
+        pub proposal_type: ProposalType,
+        pub voting_deadline: u64,
+        pub turnout: u32, // Number of members who voted
+    }
+
+    /// Linear payout of a passed money request
+    #[derive(Debug, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Stream {
+        pub id: u32,
+        pub proposal_id: u32,
+        pub recipient: H160,
+        pub asset: Option<H160>,
+        pub total: Balance,
+        pub withdrawn: Balance,
+        pub start_block: u64,
+        pub end_block: u64,
+        pub cancelled_at: Option<u64>,
+    }
+
+    /// Named budget that money requests can draw from
+    #[derive(Debug, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Budget {
+        pub name: String,
+        pub asset: Option<H160>,
+        pub allocated: Balance,
+        pub spent: Balance,
+    }
+
+    /// Remark on a proposal, the text itself is kept off-chain
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Comment {
+        pub author: H160,
+        pub text_hash: [u8; 32], // Hash of the text, e.g. of an IPFS document
+        pub block: u64,
+    }
+
+    /// Randomness requested by a passed draw proposal, consumed by `complete_draw`
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct PendingDraw {
+        pub oracle: H160,
+        pub request_id: u64,
+        pub candidates: Vec<H160>, // Members when the randomness was requested, in order
+    }
+
+    /// Sub-group of members that decides scoped spends from a budget envelope
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Committee {
+        pub id: u32,
+        pub name: String,
+        pub members: Vec<H160>,
+        pub envelope: String,
+        pub quorum: u32,
+        pub max_request: Balance,
+        pub dissolved: bool,
+    }
+
+    /// Spend from a committee envelope, decided by committee members only
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct CommitteeProposal {
+        pub id: u32,
+        pub committee_id: u32,
+        pub author: H160,
+        pub recipient: H160,
+        pub amount: Balance,
+        pub description: String,
+        pub approvals: Vec<H160>,
+        pub rejections: Vec<H160>,
+        pub status: ProposalStatus,
+        pub voting_deadline: u64,
+    }
+
+    /// Multi-tranche grant; every tranche after the first is released by a progress vote
+    #[derive(Debug, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Grant {
+        pub id: u32,
+        pub proposal_id: u32,
+        pub recipient: H160,
+        pub asset: Option<H160>,
+        pub tranches: Vec<Balance>,
+        pub released: u32,             // Number of tranches paid out
+        pub pending_vote: Option<u32>, // Open progress vote proposal
+        pub clawed_back: bool,
+    }
+
+    /// Small payout approved by a few members without a full proposal cycle
+    #[derive(Debug, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Tip {
+        pub id: u32,
+        pub proposer: H160,
+        pub recipient: H160,
+        pub amount: Balance,
+        pub reason: String,
+        pub approvals: Vec<H160>,
+        pub paid: bool,
+        pub created_at: u64,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum ApplicationStatus {
+        Pending,
+        Approved,
+        Rejected,
+    }
+
+    /// Request of a non-member to join the DAO
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Application {
+        pub id: u32,
+        pub applicant: H160,
+        pub motivation: String,
+        pub endorsements: Vec<H160>,
+        pub rejections: Vec<H160>,
+        pub status: ApplicationStatus,
+        pub created_at: u64,
+    }
+
+    /// Human-readable identity a member shows next to votes and proposals
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct MemberProfile {
+        pub name: String,
+        pub avatar_uri: String,
+        pub contact_hash: [u8; 32], // Hash of off-chain contact details
+    }
+
+    /// Self-description aggregators index DAOs by, in the spirit of EIP-4824
+    #[derive(Debug, Default, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct DaoMetadata {
+        pub name: String,
+        pub description: String,
+        pub dao_uri: String, // Points to the full JSON-LD description of the DAO
+        pub governance_docs_hash: [u8; 32], // Hash of the governance document
+    }
+
+    /// Action a member signs off-chain for a relayer to submit on their behalf
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum SignedAction {
+        Vote {
+            proposal_id: u32,
+            option: u32,
+        },
+        CreateProposal {
+            name: String,
+            description: String,
+            proposal_type: Box<ProposalType>, // Boxed to keep `Vote` actions small
+            options: Vec<String>,
+            amount: Option<Balance>,
+        },
+    }
+
+    /// Signature of a member over `meta_tx_hash`, submitted by a relayer
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct MetaTx {
+        pub signer: H160,
+        pub nonce: u64,          // Must equal `get_nonce(signer)`
+        pub signature: [u8; 65], // Recoverable ECDSA signature, `r ++ s ++ v`
+    }
+
+    /// Membership offered by a passed `AddMember` proposal
+    #[derive(Debug, Clone, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Invitation {
+        pub proposal_id: u32,
+        pub tokens: Balance,
+        pub expires_at: u64,
+    }
+
+    /// What a locked deposit was paid for
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum DepositReason {
+        Proposal(u32),
+    }
+
+    #[derive(Debug, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Deposit {
+        pub owner: H160,
+        pub amount: Balance,
+    }
+
+    /// Governance-approved payment made every `interval` blocks until `end_block`
+    #[derive(Debug, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct RecurringPayment {
+        pub id: u32,
+        pub proposal_id: u32,
+        pub recipient: H160,
+        pub asset: Option<H160>,
+        pub amount: Balance,
+        pub interval: u64,
+        pub next_payment_block: u64,
+        pub end_block: u64,
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum MilestoneStatus {
+        Pending,   // Work in progress
+        Submitted, // Recipient claims completion, members review
+        Disputed,  // Rejected by reviewers, may be resubmitted
+        Released,
+    }
+
+    #[derive(Debug, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Milestone {
+        pub amount: Balance,
+        pub status: MilestoneStatus,
+        pub approvals: Vec<H160>,
+        pub rejections: Vec<H160>,
+    }
+
+    /// Funds of a passed money request locked until its milestones are approved
+    #[derive(Debug, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Escrow {
+        pub id: u32,
+        pub proposal_id: u32,
+        pub recipient: H160,
+        pub asset: Option<H160>,
+        pub milestones: Vec<Milestone>,
+        pub cancelled: bool,
+    }
+
+    #[ink(storage)]
+    pub struct SimpleDao {
+        members: Mapping<H160, MemberInfo>,
+        member_count: u32,
+        member_positions: Mapping<u32, H160>, // Enumeration index -> member
+        member_tokens: Mapping<H160, Balance>,
+        total_supply: Balance,
+
+        proposals: Mapping<u32, Proposal>,
+        proposal_descriptions: Mapping<u32, String>, // Kept apart so voting doesn't decode them
+        ballots: Mapping<(u32, H160), Ballot>,       // (proposal, member) -> ballot
+        comments: Mapping<(u32, u32), Comment>,      // (proposal, index) -> comment
+        comment_counts: Mapping<u32, u32>,
+        next_proposal_id: u32,
+        active_proposal_count: u32,
+        active_proposal_positions: Mapping<u32, u32>, // Enumeration index -> active proposal
+        active_proposal_indices: Mapping<u32, u32>,   // Active proposal -> enumeration index
+        deadline_queues: Mapping<(u64, u32), QueuedProposal>, // (voting period, sequence) -> (proposal, deadline)
+        deadline_queue_bounds: Mapping<u64, (u32, u32)>,      // Voting period -> (head, tail)
+        queued_periods: Lazy<Vec<u64>>, // Each period's queue is in deadline order
+
+        voting_period: u64, // in blocks
+        min_votes_required: u32,
+        pass_threshold: u8, // Percent of the votes the winning option must exceed
+        type_configs: Mapping<u8, TypeConfig>, // Proposal kind -> rules replacing the global ones
+        rate_limit: RateLimit,
+        proposal_counts: Mapping<H160, (u64, u32)>, // Author -> (epoch, proposals created in it)
+        frozen: Mapping<H160, ()>, // Members locked out by a `FreezeMember` proposal
+        badges: Mapping<H160, Vec<Badge>>,
+        dispute_bond: Balance,
+        arbiter: Option<H160>,
+        disputes: Mapping<u32, u32>, // Contested proposal -> open dispute proposal
+        jury_sizes: Mapping<u8, u32>, // Proposal kind -> jurors drawn for each proposal
+        juries: Mapping<u32, Vec<H160>>, // Proposal -> members whose votes count
+        runoff_period: u64,
+        runoffs: Mapping<u32, u32>,          // First round -> runoff
+        runoff_rounds: Mapping<u32, Runoff>, // Runoff -> first round
+        quorum_mode: QuorumMode,
+        content_hashes: Mapping<u32, [u8; 32]>, // Proposal -> Keccak-256 of its `ProposalContent`
+        voting_power_snapshots: Mapping<u32, u32>, // Proposal -> total voting power at creation
+        vote_decays: Mapping<u8, u8>,           // Proposal kind -> early vote bonus percent
+        quorum_modes: Mapping<u32, QuorumMode>, // Proposal -> mode at creation, absent for `Votes`
+        tier_counts: Mapping<Tier, u32>, // Members per tier, summed into the total voting power
+        total_reputation: u64,           // Summed over members
+        reputation_holders: u32,         // Members with a reputation above 0
+        total_staked: Balance,
+
+        registered_assets: Lazy<Vec<H160>>,
+        asset_balances: Mapping<H160, Balance>,
+        reserved_funds: Mapping<Option<H160>, Balance>, // Committed but not yet paid out
+        pending_requests: Mapping<Option<H160>, Balance>, // Passed requests awaiting execution
+
+        streams: Mapping<u32, Stream>,
+        next_stream_id: u32,
+
+        escrows: Mapping<u32, Escrow>,
+        next_escrow_id: u32,
+
+        recurring_payments: Mapping<u32, RecurringPayment>,
+        active_recurring_payments: Lazy<Vec<u32>>,
+        next_recurring_payment_id: u32,
+
+        deposits: Mapping<DepositReason, Deposit>,
+        refunds: Mapping<H160, Balance>, // Released deposits awaiting `claim_refund`
+        proposal_deposit: Balance,
+
+        budgets: Mapping<String, Budget>,
+        budget_names: Lazy<Vec<String>>,
+
+        donations: Mapping<H160, Balance>, // Cumulative native deposits per donor
+        top_donors: Lazy<Vec<(H160, Balance)>>, // Largest donors first, `MAX_TOP_DONORS` at most
+
+        max_request_percent: Option<u8>, // Cap of a money request relative to the treasury
+
+        tips: Mapping<u32, Tip>,
+        next_tip_id: u32,
+        tip_cap: Balance,
+        tip_approvals_required: u32,
+        tip_budget: Balance, // Tips paid out per `tip_period` blocks
+        tip_period: u64,
+        tips_spent: (u64, Balance), // Budget period and the amount already paid in it
+
+        grants: Mapping<u32, Grant>,
+        next_grant_id: u32,
+
+        vote_rebate: Balance,
+        max_rebate_per_proposal: Balance,
+        rebates: Mapping<H160, Balance>, // Accrued voter rebates awaiting `claim_rebate`
+
+        claimable: Mapping<PayoutKey, Balance>, // Payouts awaiting `claim_payout`
+        applications: Mapping<u32, Application>,
+        next_application_id: u32,
+        pending_applications: Mapping<H160, u32>, // Applicant -> open application
+        membership_endorsements: u32,
+        membership_grant: Balance,
+        invitations: Mapping<H160, Invitation>,
+        roles: Mapping<H160, Role>, // Members without an entry have `Role::Member`
+        membership_nfts: Mapping<H160, u32>, // Member -> soulbound membership token id
+        membership_nft_owners: Mapping<u32, H160>,
+        next_membership_nft_id: u32,
+        profiles: Mapping<H160, MemberProfile>,
+        pending_transfers: Mapping<H160, H160>, // Member -> address taking over the membership
+        membership_term: u64,
+        renewal_fee: Balance,
+        membership_expiry: Mapping<H160, u64>, // Block a membership lapses at
+        lapsed_members: Lazy<Vec<H160>>,       // Expired members found by the last sweeps
+        kick_policy: KickPolicy,
+        escrowed_tokens: Mapping<H160, Balance>, // Tokens of removed members kept for their return
+        member_tiers: Mapping<H160, Tier>,       // Members without an entry are in the default tier
+        tier_configs: Mapping<Tier, TierConfig>,
+        observers: Lazy<Vec<H160>>, // Affiliated non-members without voting rights
+        allowlist: Mapping<H160, ()>,
+        kicked: Mapping<H160, ()>, // Kicked accounts, barred from buying back in
+        join_fee: Balance,
+        buy_in_tokens: Balance,
+        buy_in_price: Balance,
+        buy_in_period: u64,
+        buy_in_cap: Balance,
+        buy_in_period_start: u64,
+        buy_in_issued: Balance, // Tokens bought in during the current period
+        slashes: Mapping<H160, Vec<SlashRecord>>, // Kept after the member leaves
+        wrapped_token: Option<H160>,
+        wrapped_deposits: Mapping<H160, Balance>, // External tokens backing a holder's shares
+        wrapped_total: Balance,
+        airdrop_root: Option<[u8; 32]>,
+        airdrop_round: u32,
+        airdrop_claims: Mapping<(u32, H160), ()>, // (round, account) pairs that claimed
+        emission_amount: Balance,                 // Tokens minted by the next `mint_emission`
+        emission_epoch_length: u64,
+        emission_decay_percent: u8,
+        next_emission_block: u64,
+        reputation: Mapping<H160, u32>,
+        reputation_earned: Mapping<H160, (u64, u32)>, // Epoch and reputation earned in it
+        vote_weight_source: VoteWeightSource,
+        external_token: Option<ExternalToken>,
+        vote_records: Mapping<H160, VoteRecord>,
+        closed_proposals: u32,
+        total_participation: Participation, // Members and ballots counted at each close
+        proposal_rebates: Mapping<u32, Balance>, // Closed proposal -> rebate per voter
+        passed_proposals: u32,
+        rejected_proposals: u32,
+        expired_proposals: u32,
+        draft_proposals: u32,
+        cancelled_proposals: u32,
+        vetoed_proposals: u32,
+        treasury_inflow: Balance, // Cumulative native income, see `DaoStats`
+        treasury_outflow: Balance, // Cumulative native spending
+        committees: Mapping<u32, Committee>,
+        randomness_oracle: Option<H160>,
+        executor: Option<H160>, // Executes passed proposals instead of members when set
+        hooks: Lazy<Vec<Hook>>,
+        subscribers: Mapping<Subscription, Vec<H160>>,
+        notifications: Mapping<(H160, u32), Notification>, // (subscriber, sequence) -> unread event
+        notification_bounds: Mapping<H160, (u32, u32)>, // Subscriber -> (head, tail) of the queue
+        metadata: Lazy<DaoMetadata>,
+        pending_draws: Mapping<u32, PendingDraw>, // Proposal -> randomness it waits for
+        next_committee_id: u32,
+        committee_proposals: Mapping<u32, CommitteeProposal>,
+        next_committee_proposal_id: u32,
+        committee_seats: Mapping<H160, Vec<u32>>, // Member -> committees they sit on
+        open_committee_spends: Mapping<u32, Vec<u32>>, // Committee -> spends still voted on
+        max_members: u32,
+        resignation_policy: ResignationPolicy,
+        treasury_tokens: Balance, // Governance tokens owned by the DAO, part of the supply
+        allowances: Mapping<(H160, H160), Balance>, // (owner, spender) -> governance token allowance
+        member_only_transfers: bool,
+        vesting: Mapping<H160, VestingSchedule>,
+        vested_vote_power: bool,
+        staked: Mapping<H160, Balance>, // Tokens locked for voting weight, part of the balance
+        delegates: Mapping<H160, H160>, // Staker -> account voting with their stake, absent for self
+        voting_power: Mapping<H160, Balance>, // Stake delegated to an account, its own included
+        delegated: Mapping<H160, DelegatedStake>, // Account -> stake delegated through it
+        voting_power_checkpoints: Mapping<H160, Vec<Checkpoint>>,
+        unbonding: Mapping<H160, Unbonding>,
+        unbonding_period: u64,
+        dividend_per_token: u128, // Cumulative native revenue per held token, scaled by DIVIDEND_PRECISION
+        dividend_checkpoints: Mapping<H160, u128>, // `dividend_per_token` when a holder was last settled
+        dividends: Mapping<H160, Balance>,         // Settled, unclaimed dividends
+        balance_checkpoints: Mapping<H160, Vec<Checkpoint>>, // Balance history, ordered by block
+        token_name: Lazy<Option<String>>,          // Only read by the metadata queries
+        token_symbol: Lazy<Option<String>>,
+        token_decimals: u8,
+        soulbound: bool,
+        upgrade_timelock: u64,
+        timelocks: Mapping<u32, u64>, // Passed timelocked proposal -> first block it may execute
+        code_version: u32,            // Bumped by every executed upgrade
+        paused: bool,
+        pause_quorum: u32,
+        pause_round: u32, // Bumped on unpause, invalidating earlier pause calls
+        pause_calls: Mapping<(u32, H160), ()>, // (round, member) pairs that called `pause`
+        pause_call_count: u32, // Pause calls in the current round
+        council: Lazy<Vec<H160>>, // Emergency council, elected by governance
+        council_approvals: Mapping<CouncilApproval, ()>,
+        council_term: u64,
+        council_seats: u32,
+        council_term_end: Option<u64>, // Block the seats of an elected council lapse at
+        council_candidates: Lazy<Vec<H160>>,
+        council_election: Option<u32>, // Election of the next council, until it is seated
+        council_ballots: Mapping<u32, Vec<H160>>, // Election -> candidates in option order
+        council_terms: Mapping<u32, CouncilTerm>, // Every council seated, oldest first
+        council_term_count: u32,
+        // Set while an external call is in flight. Kept in its own cell so the write reaches
+        // storage before the call, unlike the root fields which are only written back when
+        // the message returns.
+        entered: Lazy<bool>,
+        chain_id: u64,
+        nonces: Mapping<H160, u64>, // Signer -> next meta-transaction nonce
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DaoInitiated {
+        #[ink(topic)]
+        members: Vec<H160>,
+        total_supply: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RunoffOpened {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        runoff_id: u32,
+        options: [u32; 2],
+        voting_deadline: u64,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ProposalCreated {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        author: H160,
+        name: String,
+        proposal_type: ProposalType,
+        options_count: u32,
+        voting_deadline: u64,
+        recipient: Option<H160>, // Payee of a money request
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct VoteCast {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        voter: H160,
+        option: u32,
+        weight: u32,
+        status: ProposalStatus, // Proposal status after counting the vote
+    }
+
+    /// Voting on a proposal closed, whether by votes, expiry, fast-tracking or flagging
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ProposalFinalized {
+        #[ink(topic)]
+        proposal_id: u32,
+        status: ProposalStatus,
+        votes: Vec<u32>,
+        turnout: u32,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ProposalExpired {
+        #[ink(topic)]
+        proposal_id: u32,
+        voting_deadline: u64,
+    }
+
+    /// A governance parameter was changed by an executed proposal
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ConfigChanged {
+        #[ink(topic)]
+        proposal_id: u32,
+        change: ProposalType,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct TokensDistributed {
+        #[ink(topic)]
+        recipient: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct TreasuryDeposit {
+        #[ink(topic)]
+        from: H160,
+        #[ink(topic)]
+        asset: Option<H160>,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct AssetRegistered {
+        #[ink(topic)]
+        asset: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ProposalExecuted {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        executor: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct StreamCreated {
+        #[ink(topic)]
+        stream_id: u32,
+        #[ink(topic)]
+        recipient: H160,
+        proposal_id: u32,
+        total: Balance,
+        end_block: u64,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct StreamWithdrawn {
+        #[ink(topic)]
+        stream_id: u32,
+        #[ink(topic)]
+        recipient: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct StreamCancelled {
+        #[ink(topic)]
+        stream_id: u32,
+        refunded: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct EscrowCreated {
+        #[ink(topic)]
+        escrow_id: u32,
+        #[ink(topic)]
+        recipient: H160,
+        proposal_id: u32,
+        total: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MilestoneSubmitted {
+        #[ink(topic)]
+        escrow_id: u32,
+        milestone: u32,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MilestoneReleased {
+        #[ink(topic)]
+        escrow_id: u32,
+        milestone: u32,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MilestoneDisputed {
+        #[ink(topic)]
+        escrow_id: u32,
+        milestone: u32,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct EscrowCancelled {
+        #[ink(topic)]
+        escrow_id: u32,
+        refunded: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RecurringPaymentCreated {
+        #[ink(topic)]
+        payment_id: u32,
+        #[ink(topic)]
+        recipient: H160,
+        amount: Balance,
+        interval: u64,
+        end_block: u64,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RecurringPaymentPaid {
+        #[ink(topic)]
+        payment_id: u32,
+        #[ink(topic)]
+        recipient: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RecurringPaymentFailed {
+        #[ink(topic)]
+        payment_id: u32,
+        error: Error,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RecurringPaymentCancelled {
+        #[ink(topic)]
+        payment_id: u32,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DepositLocked {
+        #[ink(topic)]
+        owner: H160,
+        reason: DepositReason,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DepositRefunded {
+        #[ink(topic)]
+        owner: H160,
+        reason: DepositReason,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DepositForfeited {
+        #[ink(topic)]
+        owner: H160,
+        reason: DepositReason,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RefundClaimed {
+        #[ink(topic)]
+        account: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct BudgetAllocated {
+        envelope: String,
+        amount: Balance,
+        allocated: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct HookRegistered {
+        #[ink(topic)]
+        contract: H160,
+        ref_time_limit: u64,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct HookUnregistered {
+        #[ink(topic)]
+        contract: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct SubscriptionChanged {
+        #[ink(topic)]
+        subscriber: H160,
+        subscription: Subscription,
+        subscribed: bool,
+    }
+
+    /// A hook reverted or ran out of gas, governance carried on without it
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct HookFailed {
+        #[ink(topic)]
+        contract: H160,
+        event: HookEvent,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DaoMetadataUpdated {
+        #[ink(topic)]
+        proposal_id: u32,
+        dao_uri: String,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MetaTxRelayed {
+        #[ink(topic)]
+        signer: H160,
+        #[ink(topic)]
+        relayer: H160,
+        nonce: u64,
+    }
+
+    /// The executor carried out a passed proposal outside the DAO
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ExecutedExternally {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        executor: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RandomnessRequested {
+        #[ink(topic)]
+        proposal_id: u32,
+        oracle: H160,
+        request_id: u64,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct BadgeAwarded {
+        #[ink(topic)]
+        member: H160,
+        badge: Badge,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CommentPosted {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        author: H160,
+        index: u32,
+        text_hash: [u8; 32],
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct JuryDrawn {
+        #[ink(topic)]
+        proposal_id: u32,
+        jurors: Vec<H160>,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CommitteeDrawn {
+        #[ink(topic)]
+        proposal_id: u32,
+        committee_id: u32,
+        members: Vec<H160>,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct LotteryWon {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        winner: H160,
+        prize: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CommitteeChartered {
+        #[ink(topic)]
+        committee_id: u32,
+        name: String,
+        envelope: String,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CommitteeDissolved {
+        #[ink(topic)]
+        committee_id: u32,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CommitteeProposalCreated {
+        #[ink(topic)]
+        committee_id: u32,
+        #[ink(topic)]
+        proposal_id: u32,
+        recipient: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CommitteeProposalDecided {
+        #[ink(topic)]
+        committee_id: u32,
+        #[ink(topic)]
+        proposal_id: u32,
+        status: ProposalStatus,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct BudgetSpent {
+        #[ink(topic)]
+        proposal_id: u32,
+        envelope: String,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DonationReceived {
+        #[ink(topic)]
+        donor: H160,
+        amount: Balance,
+        total: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct TipProposed {
+        #[ink(topic)]
+        tip_id: u32,
+        #[ink(topic)]
+        recipient: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct TipApproved {
+        #[ink(topic)]
+        tip_id: u32,
+        #[ink(topic)]
+        approver: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct TipPaid {
+        #[ink(topic)]
+        tip_id: u32,
+        #[ink(topic)]
+        recipient: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct GrantCreated {
+        #[ink(topic)]
+        grant_id: u32,
+        #[ink(topic)]
+        recipient: H160,
+        proposal_id: u32,
+        total: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct GrantTrancheReleased {
+        #[ink(topic)]
+        grant_id: u32,
+        tranche: u32,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct GrantClawedBack {
+        #[ink(topic)]
+        grant_id: u32,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RebatesAccrued {
+        #[ink(topic)]
+        proposal_id: u32,
+        per_voter: Balance,
+        voters: u32,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RebateClaimed {
+        #[ink(topic)]
+        voter: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct PayoutCredited {
+        #[ink(topic)]
+        recipient: H160,
+        #[ink(topic)]
+        asset: Option<H160>,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RevenueDistributed {
+        #[ink(topic)]
+        from: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DividendsClaimed {
+        #[ink(topic)]
+        holder: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct PayoutClaimed {
+        #[ink(topic)]
+        recipient: H160,
+        #[ink(topic)]
+        asset: Option<H160>,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MembershipApplied {
+        #[ink(topic)]
+        application_id: u32,
+        #[ink(topic)]
+        applicant: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ApplicationReviewed {
+        #[ink(topic)]
+        application_id: u32,
+        #[ink(topic)]
+        reviewer: H160,
+        approve: bool,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ApplicationDecided {
+        #[ink(topic)]
+        application_id: u32,
+        status: ApplicationStatus,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MemberInvited {
+        #[ink(topic)]
+        member: H160,
+        expires_at: u64,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct InvitationDeclined {
+        #[ink(topic)]
+        member: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RageQuit {
+        #[ink(topic)]
+        member: H160,
+        burned: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RoleChanged {
+        #[ink(topic)]
+        account: H160,
+        role: Role,
+    }
+
+    /// XCM program of a proposal dispatched with the DAO as origin
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct XcmDispatched {
+        #[ink(topic)]
+        proposal_id: u32,
+        outflow: Balance, // Native treasury funds the program moved out
+    }
+
+    /// A passed sensitive proposal waits for its timelock before `execute_proposal`
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ActionQueued {
+        #[ink(topic)]
+        proposal_id: u32,
+        executable_at: u64,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ProposalFastTracked {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        admin: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ProposalCancelled {
+        #[ink(topic)]
+        proposal_id: u32,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ProposalFlagged {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        moderator: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MembershipNftMinted {
+        #[ink(topic)]
+        member: H160,
+        token_id: u32,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MembershipNftBurned {
+        #[ink(topic)]
+        member: H160,
+        token_id: u32,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ProfileUpdated {
+        #[ink(topic)]
+        member: H160,
+        name: String,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MembershipTransferInitiated {
+        #[ink(topic)]
+        from: H160,
+        #[ink(topic)]
+        to: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MembershipTransferred {
+        #[ink(topic)]
+        from: H160,
+        #[ink(topic)]
+        to: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MembershipRenewed {
+        #[ink(topic)]
+        member: H160,
+        expires_at: u64,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MembershipExpired {
+        #[ink(topic)]
+        member: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MemberKicked {
+        #[ink(topic)]
+        member: H160,
+        tokens: Balance,
+        policy: KickPolicy,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct TierChanged {
+        #[ink(topic)]
+        account: H160,
+        tier: Tier,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct TierConfigured {
+        tier: Tier,
+        vote_weight: u32,
+        can_propose: bool,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ObserverAdded {
+        #[ink(topic)]
+        observer: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ObserverRemoved {
+        #[ink(topic)]
+        observer: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ProposalSponsored {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        sponsor: H160,
+        voting_deadline: u64,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct AllowlistUpdated {
+        #[ink(topic)]
+        account: H160,
+        allowed: bool,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ReputationChanged {
+        #[ink(topic)]
+        member: H160,
+        reputation: u32,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MemberResigned {
+        #[ink(topic)]
+        member: H160,
+        tokens: Balance,
+        policy: ResignationPolicy,
+    }
+
+    /// PSP22 transfer of governance tokens
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<H160>,
+        #[ink(topic)]
+        to: Option<H160>,
+        value: Balance,
+    }
+
+    /// PSP22 allowance change of governance tokens
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: H160,
+        #[ink(topic)]
+        spender: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct VestingGranted {
+        #[ink(topic)]
+        recipient: H160,
+        amount: Balance,
+        cliff: u64,
+        duration: u64,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct VestedClaimed {
+        #[ink(topic)]
+        recipient: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct Staked {
+        #[ink(topic)]
+        member: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DelegateChanged {
+        #[ink(topic)]
+        delegator: H160,
+        from_delegate: H160,
+        to_delegate: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct Unstaked {
+        #[ink(topic)]
+        member: H160,
+        amount: Balance,
+        release_block: u64,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct PauseRequested {
+        #[ink(topic)]
+        member: H160,
+        calls: u32, // Pause calls in the current round, this one included
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DaoPaused {
+        #[ink(topic)]
+        by: H160, // Guardian, or the member whose call reached the pause quorum
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CouncilElected {
+        #[ink(topic)]
+        proposal_id: u32,
+        council: Vec<H160>,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CouncilSeated {
+        #[ink(topic)]
+        election_id: Option<u32>, // `None` if there were no more candidates than seats
+        council: Vec<H160>,
+        ends_at: u64,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CouncilTermEnded {
+        council: Vec<H160>,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CandidacyChanged {
+        #[ink(topic)]
+        candidate: H160,
+        registered: bool, // `false` once withdrawn
+    }
+
+    /// Audit trail of every emergency council action
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CouncilActionTaken {
+        #[ink(topic)]
+        proposal_id: Option<u32>, // `None` for a pause
+        #[ink(topic)]
+        member: H160,
+        action: Option<CouncilAction>, // `None` for a pause
+        approvals: u32,
+        applied: bool, // Whether this approval completed the action
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DaoUnpaused {
+        #[ink(topic)]
+        proposal_id: u32,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CodeUpgraded {
+        #[ink(topic)]
+        proposal_id: u32,
+        code_hash: H256,
+        version: u32,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MemberSlashed {
+        #[ink(topic)]
+        member: H160,
+        amount: Balance,
+        reason_hash: [u8; 32],
+        to_treasury: bool,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct TokensWrapped {
+        #[ink(topic)]
+        account: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct TokensUnwrapped {
+        #[ink(topic)]
+        account: H160,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct BuybackExecuted {
+        #[ink(topic)]
+        market: H160,
+        payment: Balance,
+        burned: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct AirdropRootSet {
+        round: u32,
+        root: [u8; 32],
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct AirdropClaimed {
+        #[ink(topic)]
+        account: H160,
+        round: u32,
+        amount: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct EmissionMinted {
+        amount: Balance,
+        next_emission_block: u64,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct BoughtIn {
+        #[ink(topic)]
+        member: H160,
+        paid: Balance,
+        tokens: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MemberAdded {
+        #[ink(topic)]
+        member: H160,
+        tokens: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MemberRemoved {
+        #[ink(topic)]
+        member: H160,
+        burned: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MemberFrozen {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        member: H160,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MemberUnfrozen {
+        #[ink(topic)]
+        proposal_id: u32,
+        #[ink(topic)]
+        member: H160,
+        migrate_to: Option<H160>,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DisputeRaised {
+        #[ink(topic)]
+        proposal_id: u32,
+        dispute_id: u32,
+        #[ink(topic)]
+        challenger: H160,
+        bond: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DisputeResolved {
+        #[ink(topic)]
+        proposal_id: u32,
+        dispute_id: u32,
+        overturned: bool,
+    }
+
+    // Custom errors
+    #[derive(Debug, PartialEq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        NotMember,
+        ProposalNotFound,
+        ProposalExpired,
+        AlreadyVoted,
+        InvalidOption,
+        InvalidProposalType,
+        InsufficientBalance,
+        EmptyMembers,
+        InvalidVotingPeriod,
+        ProposalNotPassed,
+        AlreadyExecuted,
+        AssetNotRegistered,
+        AssetAlreadyRegistered,
+        TransferFailed,
+        InvalidStreamDuration,
+        StreamNotFound,
+        StreamAlreadyCancelled,
+        NotStreamRecipient,
+        NothingToWithdraw,
+        InvalidMilestones,
+        EscrowNotFound,
+        EscrowAlreadyCancelled,
+        MilestoneNotFound,
+        NotEscrowRecipient,
+        InvalidMilestoneStatus,
+        AlreadyReviewed,
+        InvalidRecurringPayment,
+        RecurringPaymentNotFound,
+        IncorrectDeposit,
+        ProposalAlreadyFinalized,
+        VotingPeriodNotOver,
+        InvalidBudget,
+        BudgetNotFound,
+        BudgetExceeded,
+        BudgetAssetMismatch,
+        InvalidRequestCap,
+        RequestExceedsTreasuryCap,
+        InvalidTipConfig,
+        TipsDisabled,
+        TipExceedsCap,
+        TipNotFound,
+        TipAlreadyPaid,
+        AlreadyApproved,
+        InvalidTranches,
+        GrantNotFound,
+        GrantClosed,
+        GrantVotePending,
+        AlreadyMember,
+        LastMember,
+        ApplicationNotFound,
+        ApplicationPending,
+        ApplicationClosed,
+        InvalidMembershipConfig,
+        InvitationNotFound,
+        InvitationPending,
+        InvitationExpired,
+        VoteStillOpen,
+        MissingRole,
+        NoVotesCast,
+        ProfileTooLong,
+        TransferNotFound,
+        MembershipExpired,
+        RenewalNotRequired,
+        InvalidTierConfig,
+        ProposalRightsMissing,
+        NotObserver,
+        AlreadyObserver,
+        ProposalNotSponsored,
+        NotAllowlisted,
+        InvalidCommittee,
+        CommitteeNotFound,
+        CommitteeDissolved,
+        NotCommitteeMember,
+        OutOfCommitteeScope,
+        CommitteeProposalNotFound,
+        MemberLimitReached,
+        RecipientNotMember,
+        Unauthorized,
+        InvalidVesting,
+        VestingActive,
+        ZeroAmount,
+        InsufficientStake,
+        StillUnbonding,
+        NoTokenHolders,
+        Overflow,
+        SoulboundToken,
+        BuyInDisabled,
+        BuyInCapReached,
+        InvalidBuyInConfig,
+        InvalidEmission,
+        EmissionDisabled,
+        EmissionNotDue,
+        AirdropNotActive,
+        AirdropAlreadyClaimed,
+        InvalidProof,
+        InvalidBuyback,
+        SlippageExceeded,
+        WrappingDisabled,
+        WrappedDepositsOutstanding,
+        InvalidMemberLimit,
+        InvalidConfig,
+        Paused,
+        NotPaused,
+        NotCouncilMember,
+        InvalidCouncil,
+        NotSecurityProposal,
+        SensitiveAction,
+        TimelockActive,
+        UpgradeFailed,
+        NoExternalToken,
+        InvalidXcm,
+        XcmFailed,
+        NoRandomnessOracle,
+        DrawNotFound,
+        RandomnessNotReady,
+        NotExecutor,
+        InvalidHook,
+        HookNotFound,
+        MetadataTooLong,
+        ReentrantCall,
+        ChainIdNotSet,
+        InvalidNonce,
+        InvalidSignature,
+        InvalidRateLimit,
+        AccountFrozen,
+        NotFrozen,
+        DisputesDisabled,
+        InvalidWeightBlend,
+        InvalidJurySize,
+        CommentLimitReached,
+        InvalidVoteDecay,
+        InvalidElection,
+        InvalidCouncilTerms,
+        InvalidRecipient,
+        InvalidBudgetVote,
+        InvalidStatusTransition,
+        ProposalHasVotes,
+        CouncilElectionsDisabled,
+        CouncilElectionPending,
+        CouncilTermNotOver,
+        NoCouncilElection,
+        NoCandidates,
+        AlreadyCandidate,
+        NotCandidate,
+        CandidateLimitReached,
+        AlreadySubscribed,
+        NotSubscribed,
+        SubscriptionLimitReached,
+        JuryNotDrawn,
+        NotJuror,
+        DisputeWindowClosed,
+        DisputeOpen,
+        NotArbiter,
+        NameTooLong,
+        DescriptionTooLong,
+        OptionTooLong,
+        TooManyOptions,
+        NoMembers,
+        DuplicateMember,
+        ZeroSupply,
+        InvalidMinVotes,
+        DelegationCycle,
+        DelegationChainTooLong,
+        TipBudgetExceeded,
+        KickedMember,
+        ExternalTokenNotVotes,
+    }
+
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Payout recipient and asset (`None` for the native token)
+    type PayoutKey = (H160, Option<H160>);
+
+    /// Council member approving an action on a proposal
+    type CouncilApproval = (u32, CouncilAction, H160);
+
+    /// Proposal waiting in a deadline queue and its voting deadline
+    type QueuedProposal = (u32, u64);
+
+    /// Option a member voted for and the block the vote was cast at
+    type Ballot = (u32, u64);
+
+    impl SimpleDao {
+        /// Initialize the DAO with founding members
+        #[ink(constructor)]
+        pub fn new(
+            members: Vec<H160>,
+            total_supply: Balance,
+            voting_period: u64,
+            min_votes_required: u32,
+        ) -> Self {
+            Self::new_with_token(
+                members,
+                total_supply,
+                voting_period,
+                min_votes_required,
+                TokenConfig::default(),
+            )
+        }
+
+        /// Initialize the DAO with founding members, failing with a typed error instead of
+        /// trapping when the parameters are invalid
+        #[ink(constructor)]
+        pub fn try_new(
+            members: Vec<H160>,
+            total_supply: Balance,
+            voting_period: u64,
+            min_votes_required: u32,
+        ) -> Result<Self> {
+            if members.is_empty() {
+                return Err(Error::NoMembers);
+            }
+            if members.len() > MAX_MEMBERS as usize {
+                return Err(Error::InvalidMemberLimit);
+            }
+            if members
+                .iter()
+                .enumerate()
+                .any(|(i, member)| members[..i].contains(member))
+            {
+                return Err(Error::DuplicateMember);
+            }
+            if total_supply == 0 {
+                return Err(Error::ZeroSupply);
+            }
+            if voting_period == 0 {
+                return Err(Error::InvalidVotingPeriod);
+            }
+            if min_votes_required == 0 || min_votes_required as usize > members.len() {
+                return Err(Error::InvalidMinVotes);
+            }
+
+            Ok(Self::new(
+                members,
+                total_supply,
+                voting_period,
+                min_votes_required,
+            ))
+        }
+
+        /// Initialize the DAO with founding members and governance token settings
+        #[ink(constructor)]
+        pub fn new_with_token(
+            members: Vec<H160>,
+            total_supply: Balance,
+            voting_period: u64,
+            min_votes_required: u32,
+            token: TokenConfig,
+        ) -> Self {
+            assert!(!members.is_empty(), "Invalid number of members specified.");
+            assert!(
+                members.len() <= MAX_MEMBERS as usize,
+                "Invalid number of members specified."
+            );
+            assert!(voting_period > 0, "Invalid voting period.");
+            assert!(
+                token
+                    .external
+                    .as_ref()
+                    .is_none_or(|external| external.tokens_per_vote > 0),
+                "Invalid external token."
+            );
+
+            let mut dao = Self {
+                members: Mapping::new(),
+                member_count: 0,
+                member_positions: Mapping::new(),
+                member_tokens: Mapping::new(),
+                total_supply,
+                proposals: Mapping::new(),
+                proposal_descriptions: Mapping::new(),
+                active_proposal_count: 0,
+                active_proposal_positions: Mapping::new(),
+                active_proposal_indices: Mapping::new(),
+                deadline_queues: Mapping::new(),
+                deadline_queue_bounds: Mapping::new(),
+                queued_periods: Lazy::new(),
+                ballots: Mapping::new(),
+                comments: Mapping::new(),
+                comment_counts: Mapping::new(),
+                next_proposal_id: 1,
+                voting_period,
+                min_votes_required,
+                pass_threshold: 50,
+                type_configs: Mapping::new(),
+                rate_limit: RateLimit::default(),
+                proposal_counts: Mapping::new(),
+                frozen: Mapping::new(),
+                badges: Mapping::new(),
+                dispute_bond: 0,
+                arbiter: None,
+                disputes: Mapping::new(),
+                jury_sizes: Mapping::new(),
+                juries: Mapping::new(),
+                runoff_period: 0,
+                runoffs: Mapping::new(),
+                runoff_rounds: Mapping::new(),
+                quorum_mode: QuorumMode::Votes,
+                content_hashes: Mapping::new(),
+                voting_power_snapshots: Mapping::new(),
+                vote_decays: Mapping::new(),
+                quorum_modes: Mapping::new(),
+                tier_counts: Mapping::new(),
+                total_reputation: 0,
+                reputation_holders: 0,
+                total_staked: 0,
+                registered_assets: Lazy::new(),
+                asset_balances: Mapping::new(),
+                reserved_funds: Mapping::new(),
+                pending_requests: Mapping::new(),
+                streams: Mapping::new(),
+                next_stream_id: 1,
+                escrows: Mapping::new(),
+                next_escrow_id: 1,
+                recurring_payments: Mapping::new(),
+                active_recurring_payments: Lazy::new(),
+                next_recurring_payment_id: 1,
+                deposits: Mapping::new(),
+                refunds: Mapping::new(),
+                proposal_deposit: 0,
+                budgets: Mapping::new(),
+                budget_names: Lazy::new(),
+                donations: Mapping::new(),
+                top_donors: Lazy::new(),
+                max_request_percent: None,
+                tips: Mapping::new(),
+                next_tip_id: 1,
+                tip_cap: 0,
+                tip_approvals_required: 2,
+                tip_budget: 0,
+                tip_period: 0,
+                tips_spent: (0, 0),
+                grants: Mapping::new(),
+                next_grant_id: 1,
+                vote_rebate: 0,
+                max_rebate_per_proposal: 0,
+                rebates: Mapping::new(),
+                claimable: Mapping::new(),
+                applications: Mapping::new(),
+                next_application_id: 1,
+                pending_applications: Mapping::new(),
+                membership_endorsements: min_votes_required.max(1),
+                membership_grant: 0,
+                invitations: Mapping::new(),
+                roles: Mapping::new(),
+                membership_nfts: Mapping::new(),
+                membership_nft_owners: Mapping::new(),
+                next_membership_nft_id: 1,
+                profiles: Mapping::new(),
+                pending_transfers: Mapping::new(),
+                membership_term: 0,
+                renewal_fee: 0,
+                membership_expiry: Mapping::new(),
+                lapsed_members: Lazy::new(),
+                kick_policy: KickPolicy::Burn,
+                escrowed_tokens: Mapping::new(),
+                member_tiers: Mapping::new(),
+                tier_configs: Mapping::new(),
+                observers: Lazy::new(),
+                allowlist: Mapping::new(),
+                kicked: Mapping::new(),
+                join_fee: 0,
+                buy_in_tokens: 0,
+                buy_in_price: 0,
+                buy_in_period: 0,
+                buy_in_cap: 0,
+                buy_in_period_start: 0,
+                buy_in_issued: 0,
+                slashes: Mapping::new(),
+                wrapped_token: None,
+                wrapped_deposits: Mapping::new(),
+                wrapped_total: 0,
+                airdrop_root: None,
+                airdrop_round: 0,
+                airdrop_claims: Mapping::new(),
+                emission_amount: 0,
+                emission_epoch_length: 0,
+                emission_decay_percent: 0,
+                next_emission_block: 0,
+                reputation: Mapping::new(),
+                reputation_earned: Mapping::new(),
+                // Plain PSP22 balances can't weigh votes, see `external_vote_weight`
+                vote_weight_source: if token.external.as_ref().is_some_and(|e| e.votes_style) {
+                    VoteWeightSource::External
+                } else {
+                    VoteWeightSource::Tier
+                },
+                external_token: token.external.clone(),
+                vote_records: Mapping::new(),
+                closed_proposals: 0,
+                total_participation: Participation::default(),
+                proposal_rebates: Mapping::new(),
+                passed_proposals: 0,
+                rejected_proposals: 0,
+                expired_proposals: 0,
+                draft_proposals: 0,
+                cancelled_proposals: 0,
+                vetoed_proposals: 0,
+                treasury_inflow: 0,
+                treasury_outflow: 0,
+                committees: Mapping::new(),
+                randomness_oracle: None,
+                executor: None,
+                hooks: Lazy::new(),
+                subscribers: Mapping::new(),
+                notifications: Mapping::new(),
+                notification_bounds: Mapping::new(),
+                metadata: Lazy::new(),
+                pending_draws: Mapping::new(),
+                next_committee_id: 1,
+                committee_proposals: Mapping::new(),
+                next_committee_proposal_id: 1,
+                committee_seats: Mapping::new(),
+                open_committee_spends: Mapping::new(),
+                max_members: MAX_MEMBERS,
+                resignation_policy: ResignationPolicy::Burn,
+                treasury_tokens: 0,
+                allowances: Mapping::new(),
+                member_only_transfers: true,
+                vesting: Mapping::new(),
+                vested_vote_power: false,
+                staked: Mapping::new(),
+                delegates: Mapping::new(),
+                delegated: Mapping::new(),
+                voting_power: Mapping::new(),
+                voting_power_checkpoints: Mapping::new(),
+                unbonding: Mapping::new(),
+                unbonding_period: DEFAULT_UNBONDING_PERIOD,
+                dividend_per_token: 0,
+                dividend_checkpoints: Mapping::new(),
+                dividends: Mapping::new(),
+                balance_checkpoints: Mapping::new(),
+                token_name: Lazy::new(),
+                token_symbol: Lazy::new(),
+                token_decimals: token.decimals,
+                soulbound: token.soulbound,
+                upgrade_timelock: DEFAULT_UPGRADE_TIMELOCK,
+                timelocks: Mapping::new(),
+                code_version: 1,
+                paused: false,
+                pause_quorum: 0,
+                pause_round: 0,
+                pause_calls: Mapping::new(),
+                pause_call_count: 0,
+                council: Lazy::new(),
+                council_approvals: Mapping::new(),
+                council_term: 0,
+                council_seats: 0,
+                council_term_end: None,
+                council_candidates: Lazy::new(),
+                council_election: None,
+                council_ballots: Mapping::new(),
+                council_terms: Mapping::new(),
+                council_term_count: 0,
+                entered: Lazy::new(),
+                chain_id: 0,
+                nonces: Mapping::new(),
+            };
+            dao.token_name.set(&token.name);
+            dao.token_symbol.set(&token.symbol);
+
+            // Distribute initial tokens equally among founding members
+            let tokens_per_member = total_supply / members.len() as Balance;
+            for member in &members {
+                assert!(!dao.members.contains(member), "Duplicate member.");
+                dao.insert_member(*member);
+                dao.member_tokens.insert(member, &tokens_per_member);
+                dao.checkpoint_balance(*member);
+                dao.mint_membership_nft(*member);
+            }
+
+            Self::env().emit_event(DaoInitiated {
+                members,
+                total_supply,
+            });
+
+            dao
+        }
+
+        /// Distribute additional tokens to a member (only callable by contract,
+        /// members mint through a `TokenMint` proposal)
+        #[ink(message)]
+        pub fn distribute_tokens(&mut self, recipient: H160, amount: Balance) -> Result<()> {
+            if self.env().caller() != self.env().address() {
+                return Err(Error::Unauthorized);
+            }
+
+            self.mint_tokens(recipient, amount)
+        }
+
+        /// Mint the caller's vested but unclaimed tokens
+        #[ink(message)]
+        pub fn claim_vested(&mut self) -> Result<Balance> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+
+            let mut schedule = self.vesting.get(caller).ok_or(Error::NothingToWithdraw)?;
+            let amount = self.vested(&schedule) - schedule.claimed;
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+
+            schedule.claimed += amount;
+            self.vesting.insert(caller, &schedule);
+            self.mint_tokens(caller, amount)?;
+
+            Self::env().emit_event(VestedClaimed {
+                recipient: caller,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Lock governance tokens as stake, which is what counts as voting weight
+        /// when votes are weighted by tokens
+        #[ink(message)]
+        pub fn stake(&mut self, amount: Balance) -> Result<()> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+
+            if !self.members.contains(caller) {
+                return Err(Error::NotMember);
+            }
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            if self.unlocked_tokens(caller) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            let staked = self.staked.get(caller).unwrap_or(0);
+            let staked = staked.checked_add(amount).ok_or(Error::Overflow)?;
+            self.set_stake(caller, staked);
+
+            Self::env().emit_event(Staked {
+                member: caller,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Stop staking tokens. They lose their voting weight at once but stay
+        /// locked for the unbonding period, which restarts with every unstake.
+        #[ink(message)]
+        pub fn unstake(&mut self, amount: Balance) -> Result<()> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+
+            let staked = self.staked.get(caller).unwrap_or(0);
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            if staked < amount {
+                return Err(Error::InsufficientStake);
+            }
+            self.set_stake(caller, staked - amount);
+
+            let release_block = self.env().block_number() as u64 + self.unbonding_period;
+            if self.unbonding_period > 0 {
+                let mut unbonding = self.unbonding.get(caller).unwrap_or_default();
+                unbonding.amount = unbonding
+                    .amount
+                    .checked_add(amount)
+                    .ok_or(Error::Overflow)?;
+                unbonding.release_block = release_block;
+                self.unbonding.insert(caller, &unbonding);
+            }
+
+            Self::env().emit_event(Unstaked {
+                member: caller,
+                amount,
+                release_block,
+            });
+
+            Ok(())
+        }
+
+        /// Let another member vote with the caller's stake while the tokens stay
+        /// with the caller. Delegating to oneself takes the voting power back.
+        /// Stake delegated to the caller follows along to the end of the chain,
+        /// which may not loop back or exceed `MAX_DELEGATION_HOPS`.
+        #[ink(message)]
+        pub fn delegate(&mut self, to: H160) -> Result<()> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+
+            if !self.members.contains(caller) || !self.members.contains(to) {
+                return Err(Error::NotMember);
+            }
+            if to != caller {
+                let chain = self.delegation_chain(to);
+                if chain.contains(&caller) {
+                    return Err(Error::DelegationCycle);
+                }
+                // The farthest delegator behind the caller ends up this many hops from the end
+                let behind = self.delegated.get(caller).unwrap_or_default().depths.len();
+                if behind + 1 + chain.len() > MAX_DELEGATION_HOPS {
+                    return Err(Error::DelegationChainTooLong);
+                }
+            }
+
+            let from_delegate = self.delegate_of(caller);
+            self.redelegate(caller, to);
+
+            Self::env().emit_event(DelegateChanged {
+                delegator: caller,
+                from_delegate,
+                to_delegate: to,
+            });
+
+            Ok(())
+        }
+
+        /// Deposit the configured external PSP22 token (requires prior approval)
+        /// and receive the same amount of governance tokens, members or not
+        #[ink(message)]
+        pub fn wrap(&mut self, amount: Balance) -> Result<()> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+
+            let token = self.wrapped_token.ok_or(Error::WrappingDisabled)?;
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            let deposit = self.wrapped_deposits.get(caller).unwrap_or(0);
+            let deposit = deposit.checked_add(amount).ok_or(Error::Overflow)?;
+            self.wrapped_deposits.insert(caller, &deposit);
+            self.wrapped_total = self
+                .wrapped_total
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            self.issue_tokens(caller, amount)?;
+
+            // Wrapped tokens are held for their depositors, never spent by the treasury
+            let held = self.asset_balances.get(token).unwrap_or(0);
+            self.asset_balances
+                .insert(token, &held.checked_add(amount).ok_or(Error::Overflow)?);
+            self.reserve_funds(Some(token), amount)?;
+
+            let mut external: ink::contract_ref_from_path!(PSP22) = token.into();
+            external
+                .transfer_from(caller, self.env().address(), amount, Vec::new())
+                .map_err(|_| Error::TransferFailed)?;
+
+            Self::env().emit_event(TokensWrapped {
+                account: caller,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Burn governance tokens to withdraw as many wrapped external tokens. Only shares
+        /// backed by a wrapped deposit redeem, the backing moves along with transfers.
+        #[ink(message)]
+        pub fn unwrap(&mut self, amount: Balance) -> Result<()> {
+            self.ensure_not_entered()?;
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+
+            let token = self.wrapped_token.ok_or(Error::WrappingDisabled)?;
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let deposit = self.wrapped_deposits.get(caller).unwrap_or(0);
+            if deposit < amount || self.unlocked_tokens(caller) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.settle_dividends(caller);
+            let balance = self.member_tokens.get(caller).unwrap_or(0);
+            self.member_tokens.insert(caller, &(balance - amount));
+            self.checkpoint_balance(caller);
+            self.total_supply -= amount;
+            self.wrapped_deposits.insert(caller, &(deposit - amount));
+            self.wrapped_total -= amount;
+
+            Self::env().emit_event(Transfer {
+                from: Some(caller),
+                to: None,
+                value: amount,
+            });
+
+            self.unreserve_funds(Some(token), amount);
+            self.release_funds(Some(token), caller, amount)?;
+
+            Self::env().emit_event(TokensUnwrapped {
+                account: caller,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Unlock the caller's tokens once their unbonding period has passed
+        #[ink(message)]
+        pub fn withdraw_unbonded(&mut self) -> Result<Balance> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+
+            let unbonding = self.unbonding.get(caller).ok_or(Error::NothingToWithdraw)?;
+            if (self.env().block_number() as u64) < unbonding.release_block {
+                return Err(Error::StillUnbonding);
+            }
+            self.unbonding.remove(caller);
+
+            Ok(unbonding.amount)
+        }
+
+        /// Mint the caller's allocation of the current airdrop round. `proof` holds
+        /// the sibling hashes from the leaf up to the governance-set merkle root.
+        /// Leaves are `keccak256(address ++ amount as 16 little-endian bytes)` and
+        /// pairs are hashed in ascending order.
+        #[ink(message)]
+        pub fn claim_airdrop(&mut self, proof: Vec<[u8; 32]>, amount: Balance) -> Result<()> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+
+            let root = self.airdrop_root.ok_or(Error::AirdropNotActive)?;
+            let round = self.airdrop_round;
+            if self.airdrop_claims.contains((round, caller)) {
+                return Err(Error::AirdropAlreadyClaimed);
+            }
+
+            let mut leaf = Vec::from(caller.as_bytes());
+            leaf.extend_from_slice(&amount.to_le_bytes());
+            let mut node = self.env().hash_bytes::<Keccak256>(&leaf);
+            for sibling in proof {
+                let (left, right) = if node <= sibling {
+                    (node, sibling)
+                } else {
+                    (sibling, node)
+                };
+                node = self.env().hash_bytes::<Keccak256>(&[left, right].concat());
+            }
+            if node != root {
+                return Err(Error::InvalidProof);
+            }
+
+            self.airdrop_claims.insert((round, caller), &());
+            self.mint_tokens(caller, amount)?;
+
+            Self::env().emit_event(AirdropClaimed {
+                account: caller,
+                round,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Mint the current epoch's emission into the DAO's token pool. Anyone may
+        /// call this once per epoch; missed epochs are caught up one call at a time.
+        #[ink(message)]
+        pub fn mint_emission(&mut self) -> Result<Balance> {
+            let amount = self.emission_amount;
+            if amount == 0 {
+                return Err(Error::EmissionDisabled);
+            }
+            if (self.env().block_number() as u64) < self.next_emission_block {
+                return Err(Error::EmissionNotDue);
+            }
+
+            self.total_supply = self
+                .total_supply
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            self.treasury_tokens += amount;
+            self.emission_amount =
+                amount - amount.saturating_mul(self.emission_decay_percent as Balance) / 100;
+            self.next_emission_block += self.emission_epoch_length;
+
+            Self::env().emit_event(EmissionMinted {
+                amount,
+                next_emission_block: self.next_emission_block,
+            });
+
+            Ok(amount)
+        }
+
+        /// Transfer governance tokens to another holder; only members can receive
+        /// tokens unless governance lifted the restriction
+        #[ink(message)]
+        pub fn transfer_tokens(&mut self, to: H160, amount: Balance) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            if self.soulbound {
+                return Err(Error::SoulboundToken);
+            }
+            self.ensure_not_frozen(caller)?;
+            if self.member_only_transfers && !self.members.contains(to) {
+                return Err(Error::RecipientNotMember);
+            }
+
+            self.move_tokens(caller, to, amount)
+                .map_err(Self::token_error)
+        }
+
+        /// Deposit native funds into the treasury
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) -> Result<()> {
+            let from = self.env().caller();
+            let amount = Self::to_balance(self.env().transferred_value());
+
+            Self::env().emit_event(TreasuryDeposit {
+                from,
+                asset: None,
+                amount,
+            });
+
+            if amount > 0 {
+                self.record_inflow(amount);
+                let previous = self.donations.get(from).unwrap_or(0);
+                let total = previous.checked_add(amount).ok_or(Error::Overflow)?;
+                self.donations.insert(from, &total);
+                self.rank_donor(from, total);
+
+                Self::env().emit_event(DonationReceived {
+                    donor: from,
+                    amount,
+                    total,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Deposit a registered PSP22 asset into the treasury (requires prior approval)
+        #[ink(message)]
+        pub fn deposit_asset(&mut self, asset: H160, amount: Balance) -> Result<()> {
+            if !self.registered_assets.get_or_default().contains(&asset) {
+                return Err(Error::AssetNotRegistered);
+            }
+
+            let from = self.env().caller();
+            let current_balance = self.asset_balances.get(asset).unwrap_or(0);
+            let new_balance = current_balance.checked_add(amount).ok_or(Error::Overflow)?;
+            self.asset_balances.insert(asset, &new_balance);
+
+            let mut token: ink::contract_ref_from_path!(PSP22) = asset.into();
+            token
+                .transfer_from(from, self.env().address(), amount, Vec::new())
+                .map_err(|_| Error::TransferFailed)?;
+
+            Self::env().emit_event(TreasuryDeposit {
+                from,
+                asset: Some(asset),
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Create a draft proposal as an observer; voting starts once a member sponsors it
+        #[ink(message, payable)]
+        pub fn create_draft_proposal(
+            &mut self,
+            name: String,
+            description: String,
+            proposal_type: ProposalType,
+            options: Vec<String>,
+            amount: Option<Balance>,
+        ) -> Result<u32> {
+            let caller: H160 = self.env().caller();
+
+            if !self.observers.get_or_default().contains(&caller) {
+                return Err(Error::NotObserver);
+            }
+
+            // A dispute must be decided within the timelock, it can't wait for a sponsor
+            if let ProposalType::Dispute(_) = proposal_type {
+                return Err(Error::InvalidProposalType);
+            }
+
+            self.insert_proposal(
+                caller,
+                name,
+                description,
+                proposal_type,
+                options,
+                amount,
+                MoneyRequestTerms::default(),
+                ProposalStatus::Draft,
+            )
+        }
+
+        /// Cast a vote a member signed off-chain, submitted by a relayer paying the fees
+        #[ink(message)]
+        pub fn vote_with_signature(
+            &mut self,
+            proposal_id: u32,
+            option: u32,
+            meta_tx: MetaTx,
+        ) -> Result<()> {
+            self.verify_meta_tx(
+                &meta_tx,
+                &SignedAction::Vote {
+                    proposal_id,
+                    option,
+                },
+            )?;
+            self.cast_vote(meta_tx.signer, proposal_id, option)
+        }
+
+        /// Open a proposal a member signed off-chain, submitted by a relayer paying the fees
+        /// and any deposit
+        #[ink(message, payable)]
+        pub fn create_proposal_with_signature(
+            &mut self,
+            name: String,
+            description: String,
+            proposal_type: ProposalType,
+            options: Vec<String>,
+            amount: Option<Balance>,
+            meta_tx: MetaTx,
+        ) -> Result<u32> {
+            self.verify_meta_tx(
+                &meta_tx,
+                &SignedAction::CreateProposal {
+                    name: name.clone(),
+                    description: description.clone(),
+                    proposal_type: Box::new(proposal_type.clone()),
+                    options: options.clone(),
+                    amount,
+                },
+            )?;
+            self.ensure_can_propose(meta_tx.signer)?;
+            self.insert_proposal(
+                meta_tx.signer,
+                name,
+                description,
+                proposal_type,
+                options,
+                amount,
+                MoneyRequestTerms::default(),
+                ProposalStatus::Active,
+            )
+        }
+
+        /// Record a passed proposal the executor carried out itself, without running its
+        /// on-chain effects
+        #[ink(message)]
+        pub fn mark_executed(&mut self, proposal_id: u32) -> Result<()> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            if self.executor != Some(caller) {
+                return Err(Error::NotExecutor);
+            }
+            let mut proposal = self.executable_proposal(proposal_id)?;
+            Self::set_status(&mut proposal, ProposalStatus::Executed)?;
+            self.proposals.insert(proposal_id, &proposal);
+            self.track_pending_request(&proposal, false);
+
+            Self::env().emit_event(ExecutedExternally {
+                proposal_id,
+                executor: caller,
+            });
+            self.notify_hooks(HookEvent::ProposalExecuted(proposal_id));
+
+            Ok(())
+        }
+
+        /// Carry out the draw of an executed `DrawCommittee` or `Lottery` proposal, or the jury
+        /// of an open proposal, once its randomness is available (callable by anyone)
+        #[ink(message)]
+        pub fn complete_draw(&mut self, proposal_id: u32) -> Result<()> {
+            self.ensure_not_entered()?;
+            let draw = self
+                .pending_draws
+                .get(proposal_id)
+                .ok_or(Error::DrawNotFound)?;
+            let proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            self.ensure_not_paused(&proposal.proposal_type)?;
+
+            let oracle: ink::contract_ref_from_path!(RandomnessOracle) = draw.oracle.into();
+            let randomness = oracle
+                .randomness(draw.request_id)
+                .ok_or(Error::RandomnessNotReady)?;
+            self.pending_draws.remove(proposal_id);
+            // Only the candidates fixed with the request, joining or reordering the member
+            // list after the randomness is public doesn't change the outcome
+            let candidates = draw.candidates;
+            let is_member = |candidate: &H160| self.members.contains(candidate);
+
+            if matches!(
+                proposal.status,
+                ProposalStatus::Active | ProposalStatus::Draft
+            ) {
+                let size = self
+                    .jury_sizes
+                    .get(Self::kind_of(&proposal.proposal_type))
+                    .unwrap_or(0);
+                // Governance may have dropped the jury since, then everyone votes
+                if size > 0 {
+                    let jurors =
+                        sortition::draw_eligible(randomness, &candidates, size as usize, is_member);
+                    self.juries.insert(proposal_id, &jurors);
+
+                    Self::env().emit_event(JuryDrawn {
+                        proposal_id,
+                        jurors,
+                    });
+                }
+                return Ok(());
+            }
+
+            match proposal.proposal_type {
+                ProposalType::DrawCommittee {
+                    name,
+                    size,
+                    envelope,
+                    quorum,
+                    max_request,
+                } => {
+                    // Members who left since the request are passed over
+                    let drawn =
+                        sortition::draw_eligible(randomness, &candidates, size as usize, is_member);
+                    let quorum = quorum.min(drawn.len() as u32);
+                    let committee_id =
+                        self.charter_committee(name, drawn.clone(), envelope, quorum, max_request);
+
+                    Self::env().emit_event(CommitteeDrawn {
+                        proposal_id,
+                        committee_id,
+                        members: drawn,
+                    });
+                }
+                ProposalType::Lottery { prize } => {
+                    let winner = *sortition::draw_eligible(randomness, &candidates, 1, is_member)
+                        .first()
+                        .ok_or(Error::NotMember)?;
+                    self.unreserve_funds(None, prize);
+                    self.record_outflow(prize);
+                    self.release_funds(None, winner, prize)?;
+
+                    Self::env().emit_event(LotteryWon {
+                        proposal_id,
+                        winner,
+                        prize,
+                    });
+                }
+                _ => {}
+            }
+
+            Ok(())
+        }
+
+        /// Sponsor a draft proposal, opening it for a full voting period
+        #[ink(message)]
+        pub fn sponsor_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            if !self.members.contains(caller) {
+                return Err(Error::NotMember);
+            }
+
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+
+            if proposal.status != ProposalStatus::Draft {
+                return Err(Error::ProposalAlreadyFinalized);
+            }
+            self.ensure_not_paused(&proposal.proposal_type)?;
+
+            let voting_period = self.type_config(&proposal.proposal_type).voting_period;
+            Self::set_status(&mut proposal, ProposalStatus::Active)?;
+            // Weights and the quorum are read as of sponsoring, not drafting
+            proposal.created_at = self.env().block_number() as u64;
+            proposal.voting_deadline = proposal.created_at + voting_period;
+            self.proposals.insert(proposal_id, &proposal);
+            self.open_voting(&proposal, voting_period)?;
+            self.draft_proposals -= 1;
+
+            Self::env().emit_event(ProposalSponsored {
+                proposal_id,
+                sponsor: caller,
+                voting_deadline: proposal.voting_deadline,
+            });
+
+            Ok(())
+        }
+
+        /// Contest a passed proposal during its timelock, posting the dispute bond. The proposal
+        /// can't execute until the dispute, decided by the members or the arbiter, is settled.
+        #[ink(message, payable)]
+        pub fn raise_dispute(&mut self, proposal_id: u32) -> Result<u32> {
+            self.ensure_can_propose(self.env().caller())?;
+            self.insert_proposal(
+                self.env().caller(),
+                format!("Dispute of proposal {proposal_id}"),
+                String::new(),
+                ProposalType::Dispute(proposal_id),
+                vec!["Overturn".into()],
+                None,
+                MoneyRequestTerms::default(),
+                ProposalStatus::Active,
+            )
+        }
+
+        /// Decide an open dispute (arbiter only)
+        #[ink(message)]
+        pub fn resolve_dispute(&mut self, dispute_id: u32, overturn: bool) -> Result<()> {
+            if self.arbiter != Some(self.env().caller()) {
+                return Err(Error::NotArbiter);
+            }
+
+            let mut proposal = self
+                .proposals
+                .get(dispute_id)
+                .ok_or(Error::ProposalNotFound)?;
+            if !matches!(proposal.proposal_type, ProposalType::Dispute(_)) {
+                return Err(Error::InvalidProposalType);
+            }
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalAlreadyFinalized);
+            }
+
+            let status = if overturn {
+                ProposalStatus::Passed
+            } else {
+                ProposalStatus::Rejected
+            };
+            Self::set_status(&mut proposal, status)?;
+            self.proposals.insert(dispute_id, &proposal);
+            self.on_voting_closed(&proposal);
+
+            Ok(())
+        }
+
+        /// Comment on a proposal (members and observers only)
+        #[ink(message)]
+        pub fn comment(&mut self, proposal_id: u32, text_hash: [u8; 32]) -> Result<u32> {
+            let caller: H160 = self.env().caller();
+
+            if !self.members.contains(caller) && !self.observers.get_or_default().contains(&caller)
+            {
+                return Err(Error::NotMember);
+            }
+            self.ensure_not_frozen(caller)?;
+            if !self.proposals.contains(proposal_id) {
+                return Err(Error::ProposalNotFound);
+            }
+
+            let index = self.comment_counts.get(proposal_id).unwrap_or(0);
+            if index >= MAX_COMMENTS_PER_PROPOSAL {
+                return Err(Error::CommentLimitReached);
+            }
+            let comment = Comment {
+                author: caller,
+                text_hash,
+                block: self.env().block_number() as u64,
+            };
+            self.comments.insert((proposal_id, index), &comment);
+            self.comment_counts.insert(proposal_id, &(index + 1));
+
+            Self::env().emit_event(CommentPosted {
+                proposal_id,
+                author: caller,
+                index,
+                text_hash,
+            });
+
+            Ok(index)
+        }
+
+        /// Queue governance events of a topic for the caller (members and observers only).
+        /// Watcher contracts subscribe once governance made them observers.
+        #[ink(message)]
+        pub fn subscribe(&mut self, subscription: Subscription) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            if !self.members.contains(caller) && !self.observers.get_or_default().contains(&caller)
+            {
+                return Err(Error::NotMember);
+            }
+            if let Subscription::Proposal(proposal_id) = subscription {
+                if !self.proposals.contains(proposal_id) {
+                    return Err(Error::ProposalNotFound);
+                }
+            }
+
+            let mut subscribers = self.subscribers.get(subscription).unwrap_or_default();
+            if subscribers.contains(&caller) {
+                return Err(Error::AlreadySubscribed);
+            }
+            if subscribers.len() >= MAX_SUBSCRIBERS_PER_TOPIC as usize {
+                return Err(Error::SubscriptionLimitReached);
+            }
+            subscribers.push(caller);
+            self.subscribers.insert(subscription, &subscribers);
+
+            Self::env().emit_event(SubscriptionChanged {
+                subscriber: caller,
+                subscription,
+                subscribed: true,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn unsubscribe(&mut self, subscription: Subscription) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            let mut subscribers = self.subscribers.get(subscription).unwrap_or_default();
+            let position = subscribers
+                .iter()
+                .position(|subscriber| *subscriber == caller)
+                .ok_or(Error::NotSubscribed)?;
+            subscribers.swap_remove(position);
+            if subscribers.is_empty() {
+                self.subscribers.remove(subscription);
+            } else {
+                self.subscribers.insert(subscription, &subscribers);
+            }
+
+            Self::env().emit_event(SubscriptionChanged {
+                subscriber: caller,
+                subscription,
+                subscribed: false,
+            });
+
+            Ok(())
+        }
+
+        /// Take up to `max` of the caller's unread notifications, oldest first
+        #[ink(message)]
+        pub fn drain_notifications(&mut self, max: u32) -> Vec<Notification> {
+            let subscriber: H160 = self.env().caller();
+            let (head, tail) = self.notification_bounds.get(subscriber).unwrap_or((0, 0));
+            let end = head.saturating_add(max.min(MAX_PAGE_SIZE)).min(tail);
+
+            let drained = (head..end)
+                .filter_map(|sequence| self.notifications.take((subscriber, sequence)))
+                .collect();
+            if end == tail {
+                self.notification_bounds.remove(subscriber);
+            } else {
+                self.notification_bounds.insert(subscriber, &(end, tail));
+            }
+            drained
+        }
+
+        /// Create a money request with additional terms (e.g. paid in a PSP22 asset)
+        #[ink(message, payable)]
+        pub fn create_money_request(
+            &mut self,
+            name: String,
+            description: String,
+            options: Vec<String>,
+            amount: Balance,
+            terms: MoneyRequestTerms,
+        ) -> Result<u32> {
+            self.ensure_can_propose(self.env().caller())?;
+            self.insert_proposal(
+                self.env().caller(),
+                name,
+                description,
+                ProposalType::MoneyRequest,
+                options,
+                Some(amount),
+                terms,
+                ProposalStatus::Active,
+            )
+        }
+
+        /// Close voting early and decide on the votes cast so far (admins only)
+        #[ink(message)]
+        pub fn fast_track_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            let caller: H160 = self.env().caller();
+            self.ensure_role(caller, Role::Admin)?;
+
+            let proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            // A single admin key must not rush changes to the DAO's controls
+            if Self::is_sensitive(&proposal.proposal_type) {
+                return Err(Error::SensitiveAction);
+            }
+            self.close_voting_early(proposal)?;
+
+            Self::env().emit_event(ProposalFastTracked {
+                proposal_id,
+                admin: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Approve an emergency council action on a proposal (council members only).
+        /// The action is applied once more than half of the council approved it.
+        #[ink(message)]
+        pub fn council_approve(&mut self, proposal_id: u32, action: CouncilAction) -> Result<()> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+            let council = self.sitting_council();
+            if !council.contains(&caller) {
+                return Err(Error::NotCouncilMember);
+            }
+
+            let proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            match action {
+                CouncilAction::Veto => {
+                    if proposal.status != ProposalStatus::Passed {
+                        return Err(Error::ProposalNotPassed);
+                    }
+                    // The council can't block its own replacement, its terms or a resume
+                    if matches!(
+                        proposal.proposal_type,
+                        ProposalType::ElectCouncil(_)
+                            | ProposalType::ConfigureCouncilTerms { .. }
+                            | ProposalType::Unpause
+                    ) {
+                        return Err(Error::InvalidProposalType);
+                    }
+                }
+                CouncilAction::FastTrack => {
+                    if !matches!(
+                        proposal.proposal_type,
+                        ProposalType::Upgrade(_)
+                            | ProposalType::Unpause
+                            | ProposalType::SetPauseQuorum(_)
+                            | ProposalType::FreezeMember(_)
+                    ) {
+                        return Err(Error::NotSecurityProposal);
+                    }
+                    if proposal.status != ProposalStatus::Active
+                        || self.env().block_number() as u64 > proposal.voting_deadline
+                    {
+                        return Err(Error::ProposalExpired);
+                    }
+                }
+            }
+
+            if self
+                .council_approvals
+                .contains((proposal_id, action, caller))
+            {
+                return Err(Error::AlreadyVoted);
+            }
+            self.council_approvals
+                .insert((proposal_id, action, caller), &());
+            // Approvals of members voted off the council no longer count
+            let approvals = council
+                .iter()
+                .filter(|member| {
+                    self.council_approvals
+                        .contains((proposal_id, action, **member))
+                })
+                .count() as u32;
+            let applied = approvals > council.len() as u32 / 2;
+
+            if applied {
+                match action {
+                    CouncilAction::Veto => {
+                        let mut proposal = proposal;
+                        Self::set_status(&mut proposal, ProposalStatus::Vetoed)?;
+                        self.proposals.insert(proposal_id, &proposal);
+                        self.track_pending_request(&proposal, false);
+                        self.timelocks.remove(proposal_id);
+                        self.passed_proposals -= 1;
+                        self.vetoed_proposals += 1;
+                    }
+                    CouncilAction::FastTrack => {
+                        self.close_voting_early(proposal)?;
+                        self.timelocks.remove(proposal_id);
+                    }
+                }
+            }
+
+            Self::env().emit_event(CouncilActionTaken {
+                proposal_id: Some(proposal_id),
+                member: caller,
+                action: Some(action),
+                approvals,
+                applied,
+            });
+
+            Ok(())
+        }
+
+        /// Current emergency council, empty once an elected council's term ended
+        #[ink(message)]
+        pub fn get_council(&self) -> Vec<H160> {
+            self.sitting_council()
+        }
+
+        /// Stand in the coming council elections. Candidacies carry over between terms
+        /// until withdrawn.
+        #[ink(message)]
+        pub fn register_candidacy(&mut self) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            if !self.members.contains(caller) {
+                return Err(Error::NotMember);
+            }
+            self.ensure_not_frozen(caller)?;
+            let mut candidates = self.council_candidates.get_or_default();
+            if candidates.contains(&caller) {
+                return Err(Error::AlreadyCandidate);
+            }
+            // Every candidate is an option of the election
+            if candidates.len() >= MAX_OPTIONS {
+                return Err(Error::CandidateLimitReached);
+            }
+            candidates.push(caller);
+            self.council_candidates.set(&candidates);
+
+            Self::env().emit_event(CandidacyChanged {
+                candidate: caller,
+                registered: true,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn withdraw_candidacy(&mut self) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            let mut candidates = self.council_candidates.get_or_default();
+            if !candidates.contains(&caller) {
+                return Err(Error::NotCandidate);
+            }
+            candidates.retain(|c| *c != caller);
+            self.council_candidates.set(&candidates);
+
+            Self::env().emit_event(CandidacyChanged {
+                candidate: caller,
+                registered: false,
+            });
+
+            Ok(())
+        }
+
+        /// Open the election of the next council once the sitting one's term ended
+        /// (callable by anyone, paying the deposit of an `Election`). Candidates fitting
+        /// into the seats are seated at once and no election id is returned.
+        #[ink(message, payable)]
+        pub fn start_council_election(&mut self) -> Result<Option<u32>> {
+            if self.council_term == 0 {
+                return Err(Error::CouncilElectionsDisabled);
+            }
+            // A failed election makes way for a new one
+            if let Some(election_id) = self.council_election {
+                let status = self
+                    .proposals
+                    .get(election_id)
+                    .map(|proposal| self.current_status(&proposal));
+                if matches!(
+                    status,
+                    Some(ProposalStatus::Active) | Some(ProposalStatus::Passed)
+                ) {
+                    return Err(Error::CouncilElectionPending);
+                }
+                self.council_ballots.remove(election_id);
+                self.council_election = None;
+            }
+            let now = self.env().block_number() as u64;
+            if self.council_term_end.is_some_and(|end| now < end) {
+                return Err(Error::CouncilTermNotOver);
+            }
+            self.end_council_term();
+
+            let candidates = self.council_candidates.get_or_default();
+            if candidates.is_empty() {
+                return Err(Error::NoCandidates);
+            }
+            if candidates.len() <= self.council_seats as usize {
+                self.seat_council(candidates, None);
+                return Ok(None);
+            }
+
+            let election_id = self.insert_proposal(
+                self.env().caller(),
+                "Council election".into(),
+                String::new(),
+                ProposalType::Election {
+                    seats: self.council_seats,
+                },
+                candidates.iter().map(|c| format!("{c:?}")).collect(),
+                None,
+                MoneyRequestTerms::default(),
+                ProposalStatus::Active,
+            )?;
+            self.council_ballots.insert(election_id, &candidates);
+            self.council_election = Some(election_id);
+
+            Ok(Some(election_id))
+        }
+
+        /// Seat the winners of a decided council election for one term (callable by anyone)
+        #[ink(message)]
+        pub fn seat_elected_council(&mut self) -> Result<Vec<H160>> {
+            let election_id = self.council_election.ok_or(Error::NoCouncilElection)?;
+            let result = self.get_election_result(election_id)?;
+
+            let candidates = self.council_ballots.take(election_id).unwrap_or_default();
+            // Winners who left the DAO since the vote don't take their seat
+            let council: Vec<H160> = result
+                .winners
+                .iter()
+                .map(|option| candidates[*option as usize])
+                .filter(|candidate| self.members.contains(*candidate))
+                .collect();
+            self.council_election = None;
+            self.seat_council(council.clone(), Some(election_id));
+
+            Ok(council)
+        }
+
+        #[ink(message)]
+        pub fn get_council_candidates(&self) -> Vec<H160> {
+            self.council_candidates.get_or_default()
+        }
+
+        /// Open or decided election of the next council
+        #[ink(message)]
+        pub fn get_council_election(&self) -> Option<u32> {
+            self.council_election
+        }
+
+        /// Term length, seats and the block the sitting council's term ends at
+        #[ink(message)]
+        pub fn get_council_terms(&self) -> (u64, u32, Option<u64>) {
+            (self.council_term, self.council_seats, self.council_term_end)
+        }
+
+        /// Up to `limit` councils in the order they were seated from index `start`, at
+        /// most `MAX_PAGE_SIZE` per call
+        #[ink(message)]
+        pub fn get_council_history(&self, start: u32, limit: u32) -> Vec<CouncilTerm> {
+            let end = start
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(self.council_term_count);
+            (start..end)
+                .filter_map(|index| self.council_terms.get(index))
+                .collect()
+        }
+
+        /// Withdraw a draft or an open proposal nobody voted on yet (author only), refunding
+        /// its deposit
+        #[ink(message)]
+        pub fn cancel_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            if proposal.author != caller {
+                return Err(Error::Unauthorized);
+            }
+            if proposal.voter_count > 0 {
+                return Err(Error::ProposalHasVotes);
+            }
+            // These hold other records open while they run
+            if matches!(
+                proposal.proposal_type,
+                ProposalType::Dispute(_) | ProposalType::GrantProgress(_)
+            ) {
+                return Err(Error::InvalidProposalType);
+            }
+            if self.current_status(&proposal) == ProposalStatus::Expired {
+                return Err(Error::ProposalExpired);
+            }
+            if proposal.status == ProposalStatus::Draft {
+                self.draft_proposals -= 1;
+            }
+            Self::set_status(&mut proposal, ProposalStatus::Cancelled)?;
+            self.proposals.insert(proposal_id, &proposal);
+            self.cancelled_proposals += 1;
+            self.remove_active_proposal(proposal_id);
+            self.pending_draws.remove(proposal_id);
+            self.refund_deposit(DepositReason::Proposal(proposal_id));
+
+            Self::env().emit_event(ProposalCancelled { proposal_id });
+
+            Ok(())
+        }
+
+        /// Reject an active proposal as spam (moderators and admins only)
+        #[ink(message)]
+        pub fn flag_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            let caller: H160 = self.env().caller();
+            self.ensure_role(caller, Role::Moderator)?;
+
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalAlreadyFinalized);
+            }
+
+            Self::set_status(&mut proposal, ProposalStatus::Rejected)?;
+            self.proposals.insert(proposal_id, &proposal);
+            self.on_voting_closed(&proposal);
+
+            Self::env().emit_event(ProposalFlagged {
+                proposal_id,
+                moderator: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Withdraw all refunded deposits of the caller
+        #[ink(message)]
+        pub fn claim_refund(&mut self) -> Result<Balance> {
+            self.ensure_not_entered()?;
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+            self.ensure_outflows_open()?;
+
+            let amount = self.refunds.get(caller).unwrap_or(0);
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+
+            self.refunds.remove(caller);
+            self.unreserve_funds(None, amount);
+            self.release_funds(None, caller, amount)?;
+
+            Self::env().emit_event(RefundClaimed {
+                account: caller,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Withdraw everything credited to the caller in native balance or a PSP22 asset
+        #[ink(message)]
+        pub fn claim_payout(&mut self, asset: Option<H160>) -> Result<Balance> {
+            self.ensure_not_entered()?;
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+            self.ensure_outflows_open()?;
+
+            let amount = self.claimable.get((caller, asset)).unwrap_or(0);
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+
+            self.claimable.remove((caller, asset));
+            self.unreserve_funds(asset, amount);
+            if asset.is_none() {
+                self.record_outflow(amount);
+            }
+            self.release_funds(asset, caller, amount)?;
+
+            Self::env().emit_event(PayoutClaimed {
+                recipient: caller,
+                asset,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Share the transferred native value among token holders pro-rata to their
+        /// balances. Rounding dust stays in the treasury.
+        #[ink(message, payable)]
+        pub fn distribute_revenue(&mut self) -> Result<()> {
+            let from = self.env().caller();
+            let amount = Self::to_balance(self.env().transferred_value());
+
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            // Tokens owned by the DAO don't earn dividends
+            let held_supply = self.total_supply - self.treasury_tokens;
+            if held_supply == 0 {
+                return Err(Error::NoTokenHolders);
+            }
+
+            let per_token = amount.saturating_mul(DIVIDEND_PRECISION) / held_supply;
+            let distributed = per_token.saturating_mul(held_supply) / DIVIDEND_PRECISION;
+            self.reserve_funds(None, distributed)?;
+            self.dividend_per_token = self
+                .dividend_per_token
+                .checked_add(per_token)
+                .ok_or(Error::Overflow)?;
+
+            Self::env().emit_event(RevenueDistributed {
+                from,
+                amount: distributed,
+            });
+
+            Ok(())
+        }
+
+        /// Withdraw all dividends earned by the caller's tokens
+        #[ink(message)]
+        pub fn claim_dividends(&mut self) -> Result<Balance> {
+            self.ensure_not_entered()?;
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+            self.ensure_outflows_open()?;
+
+            self.settle_dividends(caller);
+            let amount = self.dividends.take(caller).unwrap_or(0);
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+
+            self.unreserve_funds(None, amount);
+            self.release_funds(None, caller, amount)?;
+
+            Self::env().emit_event(DividendsClaimed {
+                holder: caller,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Withdraw all vote rebates accrued by the caller
+        #[ink(message)]
+        pub fn claim_rebate(&mut self) -> Result<Balance> {
+            self.ensure_not_entered()?;
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+            self.ensure_outflows_open()?;
+
+            if self.members.contains(caller) {
+                self.settle_participation(caller);
+            }
+            let amount = self.rebates.get(caller).unwrap_or(0);
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+
+            self.rebates.remove(caller);
+            self.unreserve_funds(None, amount);
+            self.record_outflow(amount);
+            self.release_funds(None, caller, amount)?;
+
+            Self::env().emit_event(RebateClaimed {
+                voter: caller,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Withdraw the accrued part of a payment stream
+        #[ink(message)]
+        pub fn withdraw_stream(&mut self, stream_id: u32) -> Result<Balance> {
+            self.ensure_not_entered()?;
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+            self.ensure_outflows_open()?;
+
+            let mut stream = self.streams.get(stream_id).ok_or(Error::StreamNotFound)?;
+
+            if stream.recipient != caller {
+                return Err(Error::NotStreamRecipient);
+            }
+
+            let amount = self.accrued(&stream) - stream.withdrawn;
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+
+            stream.withdrawn += amount;
+            self.streams.insert(stream_id, &stream);
+
+            self.unreserve_funds(stream.asset, amount);
+            if stream.asset.is_none() {
+                self.record_outflow(amount);
+            }
+            self.release_funds(stream.asset, caller, amount)?;
+
+            Self::env().emit_event(StreamWithdrawn {
+                stream_id,
+                recipient: caller,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Claim completion of an escrow milestone (only callable by the recipient)
+        #[ink(message)]
+        pub fn submit_milestone(&mut self, escrow_id: u32, milestone: u32) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            let mut escrow = self.escrows.get(escrow_id).ok_or(Error::EscrowNotFound)?;
+
+            if escrow.recipient != caller {
+                return Err(Error::NotEscrowRecipient);
+            }
+            if escrow.cancelled {
+                return Err(Error::EscrowAlreadyCancelled);
+            }
+
+            let entry = escrow
+                .milestones
+                .get_mut(milestone as usize)
+                .ok_or(Error::MilestoneNotFound)?;
+
+            // Disputed milestones can be resubmitted for a fresh review
+            if entry.status != MilestoneStatus::Pending && entry.status != MilestoneStatus::Disputed
+            {
+                return Err(Error::InvalidMilestoneStatus);
+            }
+
+            entry.status = MilestoneStatus::Submitted;
+            entry.approvals.clear();
+            entry.rejections.clear();
+            self.escrows.insert(escrow_id, &escrow);
+
+            Self::env().emit_event(MilestoneSubmitted {
+                escrow_id,
+                milestone,
+            });
+
+            Ok(())
+        }
+
+        /// Approve or reject a submitted milestone; the tranche is released once
+        /// `min_votes_required` members approve, and disputed on as many rejections
+        #[ink(message)]
+        pub fn review_milestone(
+            &mut self,
+            escrow_id: u32,
+            milestone: u32,
+            approve: bool,
+        ) -> Result<()> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+            self.ensure_outflows_open()?;
+
+            if !self.members.contains(caller) {
+                return Err(Error::NotMember);
+            }
+
+            let mut escrow = self.escrows.get(escrow_id).ok_or(Error::EscrowNotFound)?;
+
+            if escrow.cancelled {
+                return Err(Error::EscrowAlreadyCancelled);
+            }
+
+            let entry = escrow
+                .milestones
+                .get_mut(milestone as usize)
+                .ok_or(Error::MilestoneNotFound)?;
+
+            if entry.status != MilestoneStatus::Submitted {
+                return Err(Error::InvalidMilestoneStatus);
+            }
+            if entry.approvals.contains(&caller) || entry.rejections.contains(&caller) {
+                return Err(Error::AlreadyReviewed);
+            }
+
+            if approve {
+                entry.approvals.push(caller);
+            } else {
+                entry.rejections.push(caller);
+            }
+
+            let amount = entry.amount;
+            let released = entry.approvals.len() as u32 >= self.min_votes_required;
+            if released {
+                entry.status = MilestoneStatus::Released;
+            } else if entry.rejections.len() as u32 >= self.min_votes_required {
+                entry.status = MilestoneStatus::Disputed;
+
+                Self::env().emit_event(MilestoneDisputed {
+                    escrow_id,
+                    milestone,
+                });
+            }
+            self.escrows.insert(escrow_id, &escrow);
+
+            if released {
+                self.unreserve_funds(escrow.asset, amount);
+                self.credit_payout(escrow.asset, escrow.recipient, amount)?;
+                self.add_reputation(escrow.recipient, REPUTATION_PER_MILESTONE);
+
+                Self::env().emit_event(MilestoneReleased {
+                    escrow_id,
+                    milestone,
+                    amount,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Propose a small native payout; the proposer's approval is counted right away
+        #[ink(message)]
+        pub fn propose_tip(
+            &mut self,
+            recipient: H160,
+            amount: Balance,
+            reason: String,
+        ) -> Result<u32> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+            self.ensure_outflows_open()?;
+
+            if !self.members.contains(caller) {
+                return Err(Error::NotMember);
+            }
+            if self.tip_cap == 0 {
+                return Err(Error::TipsDisabled);
+            }
+            if amount == 0 || amount > self.tip_cap {
+                return Err(Error::TipExceedsCap);
+            }
+            if amount > self.tip_budget_left() {
+                return Err(Error::TipBudgetExceeded);
+            }
+
+            let tip_id = self.next_tip_id;
+            let tip = Tip {
+                id: tip_id,
+                proposer: caller,
+                recipient,
+                amount,
+                reason,
+                approvals: Vec::new(),
+                paid: false,
+                created_at: self.env().block_number() as u64,
+            };
+            self.tips.insert(tip_id, &tip);
+            self.next_tip_id += 1;
+
+            Self::env().emit_event(TipProposed {
+                tip_id,
+                recipient,
+                amount,
+            });
+
+            self.approve_tip(tip_id)?;
+
+            Ok(tip_id)
+        }
+
+        /// Approve a tip; it is paid once enough members approved
+        #[ink(message)]
+        pub fn approve_tip(&mut self, tip_id: u32) -> Result<()> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+            self.ensure_outflows_open()?;
+
+            if !self.members.contains(caller) {
+                return Err(Error::NotMember);
+            }
+
+            let mut tip = self.tips.get(tip_id).ok_or(Error::TipNotFound)?;
+
+            if tip.paid {
+                return Err(Error::TipAlreadyPaid);
+            }
+            if tip.approvals.contains(&caller) {
+                return Err(Error::AlreadyApproved);
+            }
+
+            tip.approvals.push(caller);
+            let pay = tip.approvals.len() as u32 >= self.tip_approvals_required;
+            let left = self.tip_budget_left();
+            if pay && tip.amount > left {
+                return Err(Error::TipBudgetExceeded);
+            }
+            tip.paid = pay;
+            self.tips.insert(tip_id, &tip);
+
+            Self::env().emit_event(TipApproved {
+                tip_id,
+                approver: caller,
+            });
+
+            if pay {
+                self.tips_spent = (
+                    self.tip_budget_period(),
+                    self.tip_budget - left + tip.amount,
+                );
+                self.credit_payout(None, tip.recipient, tip.amount)?;
+
+                Self::env().emit_event(TipPaid {
+                    tip_id,
+                    recipient: tip.recipient,
+                    amount: tip.amount,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Current tip budget period
+        fn tip_budget_period(&self) -> u64 {
+            (self.env().block_number() as u64)
+                .checked_div(self.tip_period)
+                .unwrap_or(0)
+        }
+
+        /// Part of the tip budget not yet paid out in the current period
+        fn tip_budget_left(&self) -> Balance {
+            let (period, spent) = self.tips_spent;
+            if period == self.tip_budget_period() {
+                self.tip_budget.saturating_sub(spent)
+            } else {
+                self.tip_budget
+            }
+        }
+
+        /// Accept a membership invitation before it expires
+        #[ink(message)]
+        pub fn accept_invitation(&mut self) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            let invitation = self
+                .invitations
+                .get(caller)
+                .ok_or(Error::InvitationNotFound)?;
+
+            if invitation.expires_at <= self.env().block_number() as u64 {
+                return Err(Error::InvitationExpired);
+            }
+            if self.members.contains(caller) {
+                return Err(Error::AlreadyMember);
+            }
+
+            self.invitations.remove(caller);
+            self.add_member(caller, invitation.tokens)?;
+
+            Ok(())
+        }
+
+        /// Decline a membership invitation
+        #[ink(message)]
+        pub fn decline_invitation(&mut self) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            if self.invitations.take(caller).is_none() {
+                return Err(Error::InvitationNotFound);
+            }
+
+            Self::env().emit_event(InvitationDeclined { member: caller });
+
+            Ok(())
+        }
+
+        /// Leave the DAO; the caller's tokens are burned or handed to the treasury
+        /// depending on the resignation policy
+        #[ink(message)]
+        pub fn resign(&mut self) -> Result<()> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+
+            if !self.members.contains(caller) {
+                return Err(Error::NotMember);
+            }
+            if self.member_count == 1 {
+                return Err(Error::LastMember);
+            }
+
+            let tokens = self.remove_member(caller);
+            if self.resignation_policy == ResignationPolicy::Treasury {
+                self.treasury_tokens = self
+                    .treasury_tokens
+                    .checked_add(tokens)
+                    .ok_or(Error::Overflow)?;
+                self.total_supply = self
+                    .total_supply
+                    .checked_add(tokens)
+                    .ok_or(Error::Overflow)?;
+            }
+
+            Self::env().emit_event(MemberResigned {
+                member: caller,
+                tokens,
+                policy: self.resignation_policy,
+            });
+
+            Ok(())
+        }
+
+        /// Leave the DAO, burning all governance tokens in exchange for a pro-rata
+        /// share of the free treasury funds in every asset. The share is credited
+        /// to `claim_payout`. Not possible while a proposal the caller voted on is open.
+        #[ink(message)]
+        pub fn rage_quit(&mut self) -> Result<Balance> {
+            self.ensure_not_entered()?;
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+            self.ensure_outflows_open()?;
+
+            if !self.members.contains(caller) {
+                return Err(Error::NotMember);
+            }
+            if self.member_count == 1 {
+                return Err(Error::LastMember);
+            }
+            for id in self.get_active_proposals() {
+                if self.ballots.contains((id, caller)) {
+                    return Err(Error::VoteStillOpen);
+                }
+            }
+
+            let total_supply = self.total_supply;
+            let burned = self.remove_member(caller);
+
+            let mut native_share = 0;
+            if burned > 0 {
+                let mut assets: Vec<Option<H160>> = vec![None];
+                assets.extend(
+                    self.registered_assets
+                        .get_or_default()
+                        .into_iter()
+                        .map(Some),
+                );
+
+                for asset in assets {
+                    // Passed money requests awaiting execution keep their funds
+                    let available = self
+                        .free_funds(asset)
+                        .saturating_sub(self.pending_requests(asset));
+                    let share = available.saturating_mul(burned) / total_supply;
+                    if share > 0 {
+                        self.credit_payout(asset, caller, share)?;
+                    }
+                    if asset.is_none() {
+                        native_share = share;
+                    }
+                }
+            }
+
+            Self::env().emit_event(RageQuit {
+                member: caller,
+                burned,
+            });
+
+            Ok(native_share)
+        }
+
+        /// Set or replace the caller's member profile
+        #[ink(message)]
+        pub fn set_member_profile(
+            &mut self,
+            name: String,
+            avatar_uri: String,
+            contact_hash: [u8; 32],
+        ) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            if !self.members.contains(caller) {
+                return Err(Error::NotMember);
+            }
+            if name.len() > MAX_PROFILE_NAME_LEN || avatar_uri.len() > MAX_AVATAR_URI_LEN {
+                return Err(Error::ProfileTooLong);
+            }
+
+            let profile = MemberProfile {
+                name: name.clone(),
+                avatar_uri,
+                contact_hash,
+            };
+            self.profiles.insert(caller, &profile);
+
+            Self::env().emit_event(ProfileUpdated {
+                member: caller,
+                name,
+            });
+
+            Ok(())
+        }
+
+        /// Extend the caller's membership by one term for the renewal fee.
+        /// Expired memberships restart from the current block.
+        #[ink(message, payable)]
+        pub fn renew_membership(&mut self) -> Result<u64> {
+            let caller: H160 = self.env().caller();
+
+            if !self.members.contains(caller) {
+                return Err(Error::NotMember);
+            }
+            if self.membership_term == 0 {
+                return Err(Error::RenewalNotRequired);
+            }
+            if Self::to_balance(self.env().transferred_value()) != self.renewal_fee {
+                return Err(Error::IncorrectDeposit);
+            }
+            self.record_inflow(self.renewal_fee);
+
+            let now = self.env().block_number() as u64;
+            let current = self.membership_expiry.get(caller).unwrap_or(now);
+            let expires_at = current.max(now) + self.membership_term;
+            self.membership_expiry.insert(caller, &expires_at);
+            let mut lapsed = self.lapsed_members.get_or_default();
+            lapsed.retain(|m| *m != caller);
+            self.lapsed_members.set(&lapsed);
+
+            Self::env().emit_event(MembershipRenewed {
+                member: caller,
+                expires_at,
+            });
+
+            Ok(expires_at)
+        }
+
+        /// Record members whose membership lapsed (callable by anyone).
+        /// Returns the number of newly lapsed members.
+        #[ink(message)]
+        pub fn sweep_expired_members(&mut self) -> u32 {
+            let mut swept = 0;
+
+            let mut lapsed = self.lapsed_members.get_or_default();
+            for member in self.member_list() {
+                if self.is_membership_expired(member) && !lapsed.contains(&member) {
+                    lapsed.push(member);
+                    swept += 1;
+
+                    Self::env().emit_event(MembershipExpired { member });
+                }
+            }
+            if swept > 0 {
+                self.lapsed_members.set(&lapsed);
+            }
+
+            swept
+        }
+
+        /// Offer the caller's membership to a new address, e.g. to rotate a key.
+        /// Replaces any earlier offer; nothing moves until the new address accepts.
+        #[ink(message)]
+        pub fn initiate_membership_transfer(&mut self, new_address: H160) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            if !self.members.contains(caller) {
+                return Err(Error::NotMember);
+            }
+            // Frozen memberships only move to an address governance picked
+            self.ensure_not_frozen(caller)?;
+            if self.members.contains(new_address) {
+                return Err(Error::AlreadyMember);
+            }
+
+            self.pending_transfers.insert(caller, &new_address);
+
+            Self::env().emit_event(MembershipTransferInitiated {
+                from: caller,
+                to: new_address,
+            });
+
+            Ok(())
+        }
+
+        /// Take over the membership offered by `old_address`, including its tokens,
+        /// role, profile, membership NFT and votes on open proposals
+        #[ink(message)]
+        pub fn accept_membership_transfer(&mut self, old_address: H160) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            if self.pending_transfers.get(old_address) != Some(caller) {
+                return Err(Error::TransferNotFound);
+            }
+            if !self.members.contains(old_address) {
+                return Err(Error::NotMember);
+            }
+            if self.members.contains(caller) {
+                return Err(Error::AlreadyMember);
+            }
+            self.pending_transfers.remove(old_address);
+            // A frozen key stays frozen, payouts and streams still addressed to it can
+            // only be reached once governance lifts the freeze
+
+            if let Some(info) = self.members.take(old_address) {
+                self.members.insert(caller, &info);
+                self.member_positions.insert(info.index, &caller);
+            }
+            let mut council = self.council.get_or_default();
+            if let Some(seat) = council.iter_mut().find(|m| **m == old_address) {
+                *seat = caller;
+                self.council.set(&council);
+            }
+            let mut candidates = self.council_candidates.get_or_default();
+            if let Some(candidate) = candidates.iter_mut().find(|c| **c == old_address) {
+                *candidate = caller;
+                self.council_candidates.set(&candidates);
+            }
+            self.move_committee_seats(old_address, caller);
+            // Shares the new address already holds are merged, not replaced
+            self.settle_dividends(old_address);
+            self.settle_dividends(caller);
+            if let Some(tokens) = self.member_tokens.take(old_address) {
+                let held = self.member_tokens.get(caller).unwrap_or(0);
+                self.member_tokens
+                    .insert(caller, &held.saturating_add(tokens));
+            }
+            self.checkpoint_balance(old_address);
+            self.checkpoint_balance(caller);
+            if let Some(role) = self.roles.take(old_address) {
+                self.roles.insert(caller, &role);
+            }
+            if let Some(tier) = self.member_tiers.take(old_address) {
+                self.member_tiers.insert(caller, &tier);
+            }
+            if let Some(reputation) = self.reputation.take(old_address) {
+                self.reputation.insert(caller, &reputation);
+            }
+            if let Some(earned) = self.reputation_earned.take(old_address) {
+                self.reputation_earned.insert(caller, &earned);
+            }
+            if let Some(record) = self.vote_records.take(old_address) {
+                self.vote_records.insert(caller, &record);
+            }
+            if let Some(schedule) = self.vesting.take(old_address) {
+                self.vesting.insert(caller, &schedule);
+            }
+            self.dividend_checkpoints.remove(old_address);
+            if let Some(dividends) = self.dividends.take(old_address) {
+                let held = self.dividends.get(caller).unwrap_or(0);
+                self.dividends
+                    .insert(caller, &held.saturating_add(dividends));
+            }
+            if let Some(deposit) = self.wrapped_deposits.take(old_address) {
+                let held = self.wrapped_deposits.get(caller).unwrap_or(0);
+                self.wrapped_deposits
+                    .insert(caller, &held.saturating_add(deposit));
+            }
+            if let Some(slashes) = self.slashes.take(old_address) {
+                self.slashes.insert(caller, &slashes);
+            }
+            if let Some(refund) = self.refunds.take(old_address) {
+                let held = self.refunds.get(caller).unwrap_or(0);
+                self.refunds.insert(caller, &held.saturating_add(refund));
+            }
+            if let Some(rebate) = self.rebates.take(old_address) {
+                let held = self.rebates.get(caller).unwrap_or(0);
+                self.rebates.insert(caller, &held.saturating_add(rebate));
+            }
+            // The new address takes the old one's place in its delegation chain,
+            // stake delegated to the old address keeps voting there
+            let delegate = self.delegate_of(old_address);
+            if delegate != old_address {
+                self.redelegate(old_address, old_address);
+            }
+            if let Some(staked) = self.staked.take(old_address) {
+                self.staked.insert(caller, &staked);
+                self.change_voting_power(old_address, 0, staked);
+                self.change_voting_power(caller, staked, 0);
+            }
+            if delegate != old_address {
+                self.redelegate(caller, delegate);
+            }
+            if let Some(unbonding) = self.unbonding.take(old_address) {
+                self.unbonding.insert(caller, &unbonding);
+            }
+            if let Some(profile) = self.profiles.take(old_address) {
+                self.profiles.insert(caller, &profile);
+            }
+            if let Some(expires_at) = self.membership_expiry.take(old_address) {
+                self.membership_expiry.insert(caller, &expires_at);
+            }
+            let mut lapsed = self.lapsed_members.get_or_default();
+            for member in lapsed.iter_mut() {
+                if *member == old_address {
+                    *member = caller;
+                }
+            }
+            self.lapsed_members.set(&lapsed);
+            if let Some(token_id) = self.membership_nfts.take(old_address) {
+                self.membership_nfts.insert(caller, &token_id);
+                self.membership_nft_owners.insert(token_id, &caller);
+            }
+
+            // Votes on open proposals follow the membership so they can't be cast twice
+            for id in self.get_active_proposals() {
+                if let Some(ballot) = self.ballots.take((id, old_address)) {
+                    self.ballots.insert((id, caller), &ballot);
+                }
+            }
+
+            Self::env().emit_event(MembershipTransferred {
+                from: old_address,
+                to: caller,
+            });
+            self.notify_hooks(HookEvent::MembershipTransferred {
+                from: old_address,
+                to: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Join as an allowlisted address, paying the join fee into the treasury.
+        /// New members receive the configured initial token grant.
+        #[ink(message, payable)]
+        pub fn join(&mut self) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            if self.members.contains(caller) {
+                return Err(Error::AlreadyMember);
+            }
+            if !self.allowlist.contains(caller) {
+                return Err(Error::NotAllowlisted);
+            }
+            if Self::to_balance(self.env().transferred_value()) != self.join_fee {
+                return Err(Error::IncorrectDeposit);
+            }
+            self.record_inflow(self.join_fee);
+
+            self.allowlist.remove(caller);
+            self.add_member(caller, self.membership_grant)?;
+
+            Ok(())
+        }
+
+        /// Become a member by paying native value into the treasury, receiving
+        /// tokens at the buy-in price while the period's cap allows it. Kicked
+        /// accounts can only come back once governance allowlists them.
+        #[ink(message, payable)]
+        pub fn buy_in(&mut self) -> Result<Balance> {
+            let caller: H160 = self.env().caller();
+            let paid = Self::to_balance(self.env().transferred_value());
+
+            if self.members.contains(caller) {
+                return Err(Error::AlreadyMember);
+            }
+            if self.kicked.contains(caller) {
+                return Err(Error::KickedMember);
+            }
+            if self.buy_in_tokens == 0 {
+                return Err(Error::BuyInDisabled);
+            }
+            let tokens = paid
+                .checked_mul(self.buy_in_tokens)
+                .ok_or(Error::Overflow)?
+                / self.buy_in_price;
+            if tokens == 0 {
+                return Err(Error::ZeroAmount);
+            }
+
+            let now = self.env().block_number() as u64;
+            if now >= self.buy_in_period_start + self.buy_in_period {
+                self.buy_in_period_start = now;
+                self.buy_in_issued = 0;
+            }
+            let issued = self
+                .buy_in_issued
+                .checked_add(tokens)
+                .ok_or(Error::Overflow)?;
+            if issued > self.buy_in_cap {
+                return Err(Error::BuyInCapReached);
+            }
+
+            self.buy_in_issued = issued;
+            self.add_member(caller, tokens)?;
+            self.record_inflow(paid);
+
+            Self::env().emit_event(BoughtIn {
+                member: caller,
+                paid,
+                tokens,
+            });
+
+            Ok(tokens)
+        }
+
+        /// Apply for membership as a non-member
+        #[ink(message)]
+        pub fn apply_for_membership(&mut self, motivation: String) -> Result<u32> {
+            let caller: H160 = self.env().caller();
+
+            if self.members.contains(caller) {
+                return Err(Error::AlreadyMember);
+            }
+            if self.pending_applications.contains(caller) {
+                return Err(Error::ApplicationPending);
+            }
+
+            let application_id = self.next_application_id;
+            let application = Application {
+                id: application_id,
+                applicant: caller,
+                motivation,
+                endorsements: Vec::new(),
+                rejections: Vec::new(),
+                status: ApplicationStatus::Pending,
+                created_at: self.env().block_number() as u64,
+            };
+            self.applications.insert(application_id, &application);
+            self.pending_applications.insert(caller, &application_id);
+            self.next_application_id += 1;
+
+            Self::env().emit_event(MembershipApplied {
+                application_id,
+                applicant: caller,
+            });
+
+            Ok(application_id)
+        }
+
+        /// Endorse or reject a pending application; it is decided once
+        /// `membership_endorsements` members agree either way
+        #[ink(message)]
+        pub fn review_application(&mut self, application_id: u32, approve: bool) -> Result<()> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+
+            if !self.members.contains(caller) {
+                return Err(Error::NotMember);
+            }
+
+            let mut application = self
+                .applications
+                .get(application_id)
+                .ok_or(Error::ApplicationNotFound)?;
+
+            if application.status != ApplicationStatus::Pending {
+                return Err(Error::ApplicationClosed);
+            }
+            if application.endorsements.contains(&caller)
+                || application.rejections.contains(&caller)
+            {
+                return Err(Error::AlreadyReviewed);
+            }
+
+            if approve {
+                application.endorsements.push(caller);
+            } else {
+                application.rejections.push(caller);
+            }
+
+            Self::env().emit_event(ApplicationReviewed {
+                application_id,
+                reviewer: caller,
+                approve,
+            });
+
+            if application.endorsements.len() as u32 >= self.membership_endorsements {
+                application.status = ApplicationStatus::Approved;
+            } else if application.rejections.len() as u32 >= self.membership_endorsements {
+                application.status = ApplicationStatus::Rejected;
+            }
+            self.applications.insert(application_id, &application);
+
+            if application.status != ApplicationStatus::Pending {
+                self.pending_applications.remove(application.applicant);
+                if application.status == ApplicationStatus::Approved
+                    && !self.members.contains(application.applicant)
+                {
+                    self.add_member(application.applicant, self.membership_grant)?;
+                }
+
+                Self::env().emit_event(ApplicationDecided {
+                    application_id,
+                    status: application.status,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Propose a spend from a committee envelope, within the committee's scope
+        #[ink(message)]
+        pub fn propose_committee_spend(
+            &mut self,
+            committee_id: u32,
+            recipient: H160,
+            amount: Balance,
+            description: String,
+        ) -> Result<u32> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+            self.ensure_outflows_open()?;
+
+            let committee = self
+                .committees
+                .get(committee_id)
+                .ok_or(Error::CommitteeNotFound)?;
+
+            if committee.dissolved {
+                return Err(Error::CommitteeDissolved);
+            }
+            if !committee.members.contains(&caller) {
+                return Err(Error::NotCommitteeMember);
+            }
+            if amount == 0 || amount > committee.max_request {
+                return Err(Error::OutOfCommitteeScope);
+            }
+
+            let proposal_id = self.next_committee_proposal_id;
+            let proposal = CommitteeProposal {
+                id: proposal_id,
+                committee_id,
+                author: caller,
+                recipient,
+                amount,
+                description,
+                approvals: Vec::new(),
+                rejections: Vec::new(),
+                status: ProposalStatus::Active,
+                voting_deadline: self.env().block_number() as u64 + self.voting_period,
+            };
+            self.committee_proposals.insert(proposal_id, &proposal);
+            self.next_committee_proposal_id += 1;
+
+            // Spends whose vote ended undecided are dropped from the open list on the way
+            let now = self.env().block_number() as u64;
+            let mut open = self
+                .open_committee_spends
+                .get(committee_id)
+                .unwrap_or_default();
+            open.retain(|id| {
+                self.committee_proposals
+                    .get(id)
+                    .is_some_and(|spend| spend.voting_deadline >= now)
+            });
+            open.push(proposal_id);
+            self.open_committee_spends.insert(committee_id, &open);
+
+            Self::env().emit_event(CommitteeProposalCreated {
+                committee_id,
+                proposal_id,
+                recipient,
+                amount,
+            });
+
+            Ok(proposal_id)
+        }
+
+        /// Approve or reject a committee spend; it is paid out from the committee
+        /// envelope once the committee quorum approves, and rejected on as many rejections
+        #[ink(message)]
+        pub fn vote_committee_spend(&mut self, proposal_id: u32, approve: bool) -> Result<()> {
+            let caller: H160 = self.env().caller();
+            self.ensure_not_frozen(caller)?;
+            self.ensure_outflows_open()?;
+
+            let mut proposal = self
+                .committee_proposals
+                .get(proposal_id)
+                .ok_or(Error::CommitteeProposalNotFound)?;
+            let committee = self
+                .committees
+                .get(proposal.committee_id)
+                .ok_or(Error::CommitteeNotFound)?;
+
+            if committee.dissolved {
+                return Err(Error::CommitteeDissolved);
+            }
+            if !committee.members.contains(&caller) {
+                return Err(Error::NotCommitteeMember);
+            }
+            if proposal.status != ProposalStatus::Active
+                || self.env().block_number() as u64 > proposal.voting_deadline
+            {
+                return Err(Error::ProposalExpired);
+            }
+            if proposal.approvals.contains(&caller) || proposal.rejections.contains(&caller) {
+                return Err(Error::AlreadyVoted);
+            }
+
+            if approve {
+                proposal.approvals.push(caller);
+            } else {
+                proposal.rejections.push(caller);
+            }
+
+            if proposal.approvals.len() as u32 >= committee.quorum {
+                proposal.status = ProposalStatus::Passed;
+            } else if proposal.rejections.len() as u32 >= committee.quorum {
+                proposal.status = ProposalStatus::Rejected;
+            }
+            self.committee_proposals.insert(proposal_id, &proposal);
+
+            if proposal.status == ProposalStatus::Passed {
+                let asset = self
+                    .budgets
+                    .get(&committee.envelope)
+                    .ok_or(Error::BudgetNotFound)?
+                    .asset;
+                self.spend_budget(&committee.envelope, asset, proposal.amount)?;
+                self.credit_payout(asset, proposal.recipient, proposal.amount)?;
+            }
+            if proposal.status != ProposalStatus::Active {
+                let mut open = self
+                    .open_committee_spends
+                    .get(committee.id)
+                    .unwrap_or_default();
+                open.retain(|id| *id != proposal_id);
+                self.open_committee_spends.insert(committee.id, &open);
+
+                Self::env().emit_event(CommitteeProposalDecided {
+                    committee_id: committee.id,
+                    proposal_id,
+                    status: proposal.status,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Pay out up to `max` due installments of recurring payments (callable by anyone).
+        /// Payments the treasury can't currently cover, or that fail, stay due for a
+        /// later call without holding up the others.
+        #[ink(message)]
+        pub fn process_payments(&mut self, max: u32) -> Result<u32> {
+            self.ensure_outflows_open()?;
+            let now = self.env().block_number() as u64;
+            let mut processed = 0;
+
+            let mut active = self.active_recurring_payments.get_or_default();
+            let count = active.len();
+            for payment_id in active.clone() {
+                if processed >= max {
+                    break;
+                }
+                let Some(mut payment) = self.recurring_payments.get(payment_id) else {
+                    continue;
+                };
+
+                while processed < max
+                    && payment.next_payment_block <= now.min(payment.end_block)
+                    && self.free_funds(payment.asset) >= payment.amount
+                {
+                    if let Err(error) =
+                        self.credit_payout(payment.asset, payment.recipient, payment.amount)
+                    {
+                        Self::env().emit_event(RecurringPaymentFailed { payment_id, error });
+                        break;
+                    }
+                    payment.next_payment_block += payment.interval;
+                    processed += 1;
+
+                    Self::env().emit_event(RecurringPaymentPaid {
+                        payment_id,
+                        recipient: payment.recipient,
+                        amount: payment.amount,
+                    });
+                }
+
+                self.recurring_payments.insert(payment_id, &payment);
+                if payment.next_payment_block > payment.end_block {
+                    active.retain(|id| *id != payment_id);
+                }
+            }
+            if active.len() != count {
+                self.active_recurring_payments.set(&active);
+            }
+
+            Ok(processed)
+        }
+
+        /// Option a member voted for, `None` if they haven't voted
+        #[ink(message)]
+        pub fn get_vote(&self, proposal_id: u32, member: H160) -> Option<u32> {
+            self.ballots
+                .get((proposal_id, member))
+                .map(|(option, _)| option)
+        }
+
+        /// Winners of a decided election. Options tied for the last seat are ranked by their
+        /// order on the ballot.
+        #[ink(message)]
+        pub fn get_election_result(&self, proposal_id: u32) -> Result<ElectionResult> {
+            let proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            let ProposalType::Election { seats } = proposal.proposal_type else {
+                return Err(Error::InvalidProposalType);
+            };
+            if self.current_status(&proposal) != ProposalStatus::Passed {
+                return Err(Error::ProposalNotPassed);
+            }
+
+            // A stable sort keeps ballot order among equal counts
+            let mut ranking: Vec<u32> = (0..proposal.votes.len() as u32).collect();
+            ranking.sort_by(|a, b| proposal.votes[*b as usize].cmp(&proposal.votes[*a as usize]));
+            let seats = seats as usize;
+            let tie_broken = ranking.get(seats).is_some_and(|runner_up| {
+                proposal.votes[*runner_up as usize] == proposal.votes[ranking[seats - 1] as usize]
+            });
+            ranking.truncate(seats);
+
+            Ok(ElectionResult {
+                winners: ranking,
+                tie_broken,
+            })
+        }
+
+        /// Keccak-256 of the SCALE-encoded `ProposalContent` a proposal was created with
+        #[ink(message)]
+        pub fn get_content_hash(&self, proposal_id: u32) -> Option<[u8; 32]> {
+            self.content_hashes.get(proposal_id)
+        }
+
+        /// Whether `blob`, a SCALE-encoded `ProposalContent`, is exactly what a proposal was
+        /// created with, for mirrors proving they show what was voted on
+        #[ink(message)]
+        pub fn verify_proposal_content(&self, proposal_id: u32, blob: Vec<u8>) -> bool {
+            self.content_hashes
+                .get(proposal_id)
+                .is_some_and(|hash| hash == self.env().hash_bytes::<Keccak256>(&blob))
+        }
+
+        /// Runoff opened when a proposal expired without a majority
+        #[ink(message)]
+        pub fn get_runoff(&self, proposal_id: u32) -> Option<u32> {
+            self.runoffs.get(proposal_id)
+        }
+
+        /// First round a runoff decides and the options it carried over
+        #[ink(message)]
+        pub fn get_runoff_round(&self, runoff_id: u32) -> Option<Runoff> {
+            self.runoff_rounds.get(runoff_id)
+        }
+
+        /// Block a member's vote was cast at, `None` if they haven't voted
+        #[ink(message)]
+        pub fn get_vote_block(&self, proposal_id: u32, member: H160) -> Option<u64> {
+            self.ballots
+                .get((proposal_id, member))
+                .map(|(_, block)| block)
+        }
+
+        /// Early vote bonus of a proposal kind, 0 if votes weigh the same throughout
+        #[ink(message)]
+        pub fn get_vote_decay(&self, kind: u8) -> u8 {
+            self.vote_decays.get(kind).unwrap_or(0)
+        }
+
+        /// Badges of a member in the order they were earned
+        #[ink(message)]
+        pub fn get_badges(&self, member: H160) -> Vec<Badge> {
+            self.badges.get(member).unwrap_or_default()
+        }
+
+        /// Accounts whose queues receive events of a topic
+        #[ink(message)]
+        pub fn get_subscribers(&self, subscription: Subscription) -> Vec<H160> {
+            self.subscribers.get(subscription).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn unread_count(&self, subscriber: H160) -> u32 {
+            let (head, tail) = self.notification_bounds.get(subscriber).unwrap_or((0, 0));
+            tail - head
+        }
+
+        /// Up to `limit` unread notifications of a subscriber without consuming them, at most
+        /// `MAX_PAGE_SIZE` per call
+        #[ink(message)]
+        pub fn get_notifications(&self, subscriber: H160, limit: u32) -> Vec<Notification> {
+            let (head, tail) = self.notification_bounds.get(subscriber).unwrap_or((0, 0));
+            let end = head.saturating_add(limit.min(MAX_PAGE_SIZE)).min(tail);
+            (head..end)
+                .filter_map(|sequence| self.notifications.get((subscriber, sequence)))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_comment_count(&self, proposal_id: u32) -> u32 {
+            self.comment_counts.get(proposal_id).unwrap_or(0)
+        }
+
+        /// Up to `limit` comments on a proposal in posting order from index `start`, at most
+        /// `MAX_PAGE_SIZE` per call
+        #[ink(message)]
+        pub fn get_comments(&self, proposal_id: u32, start: u32, limit: u32) -> Vec<Comment> {
+            let end = start
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(self.get_comment_count(proposal_id));
+            (start..end)
+                .filter_map(|index| self.comments.get((proposal_id, index)))
+                .collect()
+        }
+
+        /// Summaries of up to `limit` proposals with ids from `start_id` on,
+        /// at most `MAX_PAGE_SIZE` per call
+        #[ink(message)]
+        pub fn get_proposal_summaries(&self, start_id: u32, limit: u32) -> Vec<ProposalSummary> {
+            let end = start_id
+                .max(1)
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(self.next_proposal_id);
+            (start_id.max(1)..end)
+                .filter_map(|id| self.proposals.get(id))
+                .map(|proposal| self.summarize(proposal))
+                .collect()
+        }
+
+        /// Up to `limit` proposals past their deadline that still await `finalize_proposal`,
+        /// oldest deadline first, at most `MAX_PAGE_SIZE` per call
+        #[ink(message)]
+        pub fn get_proposals_needing_finalization(&self, limit: u32) -> Vec<u32> {
+            let now = self.env().block_number() as u64;
+            let limit = limit.min(MAX_PAGE_SIZE) as usize;
+            let mut due: Vec<(u64, u32)> = Vec::new();
+
+            for period in self.queued_periods.get_or_default() {
+                let (head, tail) = self.deadline_queue_bounds.get(period).unwrap_or((0, 0));
+                let mut taken = 0;
+                for position in head..tail {
+                    if taken >= limit {
+                        break;
+                    }
+                    let Some((id, deadline)) = self.deadline_queues.get((period, position)) else {
+                        continue;
+                    };
+                    if deadline >= now {
+                        break;
+                    }
+                    if self.awaits_deadline(id, deadline) && !due.iter().any(|&(_, d)| d == id) {
+                        due.push((deadline, id));
+                        taken += 1;
+                    }
+                }
+            }
+
+            // Merge the queues, each already in deadline order
+            due.sort_unstable();
+            due.truncate(limit);
+            due.into_iter().map(|(_, id)| id).collect()
+        }
+
+        /// Summaries of a `get_active_proposals_page` page
+        #[ink(message)]
+        pub fn get_active_proposal_summaries(
+            &self,
+            cursor: u32,
+            limit: u32,
+        ) -> (Vec<ProposalSummary>, Option<u32>) {
+            let (ids, next) = self.get_active_proposals_page(cursor, limit);
+            let summaries = ids
+                .into_iter()
+                .filter_map(|id| self.proposals.get(id))
+                .map(|proposal| self.summarize(proposal))
+                .collect();
+
+            (summaries, next)
+        }
+
+        /// Active proposals at enumeration positions `cursor..cursor + limit`, at most
+        /// `MAX_PAGE_SIZE` per call. Returns the position the next page starts at, `None`
+        /// after the last one. A closing proposal's position goes to the last active one,
+        /// which pages already walked past can miss. Expired proposals awaiting
+        /// `finalize_proposal` are skipped.
+        #[ink(message)]
+        pub fn get_active_proposals_page(
+            &self,
+            cursor: u32,
+            limit: u32,
+        ) -> (Vec<u32>, Option<u32>) {
+            let end = cursor
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(self.active_proposal_count);
+            let page = (cursor..end)
+                .filter_map(|index| self.active_proposal_positions.get(index))
+                .filter(|id| self.is_open_for_voting(*id))
+                .collect();
+            let next = (end < self.active_proposal_count).then_some(end);
+
+            (page, next)
+        }
+
+        #[ink(message)]
+        pub fn get_member_balance(&self, member: H160) -> Balance {
+            self.member_tokens.get(member).unwrap_or(0)
+        }
+
+        /// Token balance a holder had at the end of `block`
+        #[ink(message)]
+        pub fn balance_at(&self, member: H160, block: u64) -> Balance {
+            let checkpoints = self.balance_checkpoints.get(member).unwrap_or_default();
+            Self::checkpoint_at(&checkpoints, block)
+        }
+
+        /// Account whose vote carries the member's stake, the member itself unless delegated
+        #[ink(message)]
+        pub fn get_delegate(&self, member: H160) -> H160 {
+            self.delegate_of(member)
+        }
+
+        /// Account at the end of the member's delegation chain, the one actually voting
+        /// with their stake
+        #[ink(message)]
+        pub fn resolved_delegate_of(&self, member: H160) -> H160 {
+            self.resolved_delegate(member)
+        }
+
+        /// Stake reaching an account through delegation chains and the delegators behind
+        /// it by distance
+        #[ink(message)]
+        pub fn get_delegated_stake(&self, account: H160) -> DelegatedStake {
+            self.delegated.get(account).unwrap_or_default()
+        }
+
+        /// Stake an account votes with, delegated stake included
+        #[ink(message)]
+        pub fn get_voting_power(&self, account: H160) -> Balance {
+            self.voting_power.get(account).unwrap_or(0)
+        }
+
+        /// Stake an account voted with at the end of `block`
+        #[ink(message)]
+        pub fn voting_power_at(&self, account: H160, block: u64) -> Balance {
+            let checkpoints = self
+                .voting_power_checkpoints
+                .get(account)
+                .unwrap_or_default();
+            Self::checkpoint_at(&checkpoints, block)
+        }
+
+        /// All members at once, only meant for small DAOs; use `get_members_page` otherwise
+        #[ink(message)]
+        pub fn get_members(&self) -> Vec<H160> {
+            self.member_list()
+        }
+
+        /// Up to `limit` members starting at `offset`, at most `MAX_PAGE_SIZE` per call
+        #[ink(message)]
+        pub fn get_members_page(&self, offset: u32, limit: u32) -> Vec<H160> {
+            let end = offset
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(self.member_count);
+            (offset..end)
+                .filter_map(|index| self.member_positions.get(index))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn member_count(&self) -> u32 {
+            self.member_count
+        }
+
+        #[ink(message)]
+        pub fn get_max_members(&self) -> u32 {
+            self.max_members
+        }
+
+        #[ink(message)]
+        pub fn get_total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        #[ink(message)]
+        pub fn get_treasury_balance(&self) -> Balance {
+            Self::to_balance(self.env().balance())
+        }
+
+        /// Native treasury balance and how much of it is already committed
+        #[ink(message)]
+        pub fn get_treasury(&self) -> TreasuryOverview {
+            let streams = (1..self.next_stream_id)
+                .filter_map(|id| self.streams.get(id))
+                .filter(|stream| stream.asset.is_none())
+                .map(|stream| {
+                    let owed = match stream.cancelled_at {
+                        Some(_) => self.accrued(&stream),
+                        None => stream.total,
+                    };
+                    owed.saturating_sub(stream.withdrawn)
+                })
+                .fold(0, Balance::saturating_add);
+            let vesting_obligations = self
+                .member_list()
+                .into_iter()
+                .filter_map(|member| self.vesting.get(member))
+                .map(|schedule| schedule.total.saturating_sub(schedule.claimed))
+                .fold(0, Balance::saturating_add);
+            let pending_requests = self.pending_requests(None);
+
+            TreasuryOverview {
+                balance: self.held_funds(None),
+                reserved: self.reserved_funds.get(None::<H160>).unwrap_or(0),
+                streams,
+                pending_requests,
+                spendable: self.free_funds(None).saturating_sub(pending_requests),
+                vesting_obligations,
+            }
+        }
+
+        /// Payouts credited to the account and not yet claimed
+        #[ink(message)]
+        pub fn get_claimable(&self, account: H160, asset: Option<H160>) -> Balance {
+            self.claimable.get((account, asset)).unwrap_or(0)
+        }
+
+        /// Dividends the holder could claim right now with `claim_dividends`
+        #[ink(message)]
+        pub fn get_dividends(&self, holder: H160) -> Balance {
+            self.dividends.get(holder).unwrap_or(0) + self.unsettled_dividends(holder)
+        }
+
+        /// Rebates accrued by a voter, including ones for ballots not settled yet
+        #[ink(message)]
+        pub fn get_rebate(&self, voter: H160) -> Balance {
+            let pending = if self.members.contains(voter) {
+                self.settled_vote_record(voter).1
+            } else {
+                0
+            };
+            self.rebates.get(voter).unwrap_or(0).saturating_add(pending)
+        }
+
+        /// Rebate per vote and the maximum paid out per proposal
+        #[ink(message)]
+        pub fn get_rebate_config(&self) -> (Balance, Balance) {
+            (self.vote_rebate, self.max_rebate_per_proposal)
+        }
+
+        #[ink(message)]
+        pub fn get_grant(&self, grant_id: u32) -> Option<Grant> {
+            self.grants.get(grant_id)
+        }
+
+        #[ink(message)]
+        pub fn get_tip(&self, tip_id: u32) -> Option<Tip> {
+            self.tips.get(tip_id)
+        }
+
+        #[ink(message)]
+        pub fn get_invitation(&self, account: H160) -> Option<Invitation> {
+            self.invitations.get(account)
+        }
+
+        #[ink(message)]
+        pub fn get_application(&self, application_id: u32) -> Option<Application> {
+            self.applications.get(application_id)
+        }
+
+        /// Endorsements needed to decide an application and the tokens granted on approval
+        #[ink(message)]
+        pub fn get_membership_config(&self) -> (u32, Balance) {
+            (self.membership_endorsements, self.membership_grant)
+        }
+
+        /// Largest tip amount and the number of approvals needed to pay a tip
+        #[ink(message)]
+        pub fn get_tip_config(&self) -> (Balance, u32) {
+            (self.tip_cap, self.tip_approvals_required)
+        }
+
+        /// Tip budget per period, the period length in blocks and what is left of it now
+        #[ink(message)]
+        pub fn get_tip_budget(&self) -> (Balance, u64, Balance) {
+            (self.tip_budget, self.tip_period, self.tip_budget_left())
+        }
+
+        #[ink(message)]
+        pub fn get_max_request_percent(&self) -> Option<u8> {
+            self.max_request_percent
+        }
+
+        #[ink(message)]
+        pub fn get_donations(&self, donor: H160) -> Balance {
+            self.donations.get(donor).unwrap_or(0)
+        }
+
+        /// Up to `n` donors with the highest cumulative donations, largest first, from a
+        /// leaderboard of `MAX_TOP_DONORS`
+        #[ink(message)]
+        pub fn get_top_donors(&self, n: u32) -> Vec<(H160, Balance)> {
+            let mut donors = self.top_donors.get_or_default();
+            donors.truncate(n as usize);
+            donors
+        }
+
+        #[ink(message)]
+        pub fn get_registered_assets(&self) -> Vec<H160> {
+            self.registered_assets.get_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_asset_balance(&self, asset: H160) -> Balance {
+            self.asset_balances.get(asset).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_stream(&self, stream_id: u32) -> Option<Stream> {
+            self.streams.get(stream_id)
+        }
 
         #[ink(message)]
-        pub fn get_active_proposals(&self) -> Vec<u32> {
-            let mut active_proposals = Vec::new();
+        pub fn get_recurring_payment(&self, payment_id: u32) -> Option<RecurringPayment> {
+            self.recurring_payments.get(payment_id)
+        }
+
+        #[ink(message)]
+        pub fn get_active_recurring_payments(&self) -> Vec<u32> {
+            self.active_recurring_payments.get_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_proposal_deposit(&self) -> Balance {
+            self.proposal_deposit
+        }
+
+        #[ink(message)]
+        pub fn get_deposit(&self, reason: DepositReason) -> Option<Deposit> {
+            self.deposits.get(reason)
+        }
+
+        /// Released deposits the account can withdraw with `claim_refund`
+        #[ink(message)]
+        pub fn get_refund(&self, account: H160) -> Balance {
+            self.refunds.get(account).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_budget(&self, envelope: String) -> Option<Budget> {
+            self.budgets.get(&envelope)
+        }
+
+        #[ink(message)]
+        pub fn get_budgets(&self) -> Vec<Budget> {
+            self.budget_names
+                .get_or_default()
+                .iter()
+                .filter_map(|name| self.budgets.get(name))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_committee(&self, committee_id: u32) -> Option<Committee> {
+            self.committees.get(committee_id)
+        }
+
+        #[ink(message)]
+        pub fn get_committee_proposal(&self, proposal_id: u32) -> Option<CommitteeProposal> {
+            self.committee_proposals.get(proposal_id)
+        }
+
+        #[ink(message)]
+        pub fn get_escrow(&self, escrow_id: u32) -> Option<Escrow> {
+            self.escrows.get(escrow_id)
+        }
+
+        /// Amount the stream recipient could withdraw right now
+        #[ink(message)]
+        pub fn get_stream_withdrawable(&self, stream_id: u32) -> Balance {
+            self.streams
+                .get(stream_id)
+                .map(|stream| self.accrued(&stream) - stream.withdrawn)
+                .unwrap_or(0)
+        }
+
+        /// Soulbound membership token of an account. Tokens can't be transferred,
+        /// they are minted on joining and burned on leaving the DAO.
+        #[ink(message)]
+        pub fn get_membership_nft(&self, account: H160) -> Option<u32> {
+            self.membership_nfts.get(account)
+        }
+
+        /// Holder of a membership token, `None` once it was burned
+        #[ink(message)]
+        pub fn owner_of(&self, token_id: u32) -> Option<H160> {
+            self.membership_nft_owners.get(token_id)
+        }
+
+        #[ink(message)]
+        pub fn is_allowlisted(&self, account: H160) -> bool {
+            self.allowlist.contains(account)
+        }
+
+        #[ink(message)]
+        pub fn get_join_fee(&self) -> Balance {
+            self.join_fee
+        }
+
+        /// Tokens per buy-in price, the price, period length and per-period cap
+        #[ink(message)]
+        pub fn get_buy_in_config(&self) -> (Balance, Balance, u64, Balance) {
+            (
+                self.buy_in_tokens,
+                self.buy_in_price,
+                self.buy_in_period,
+                self.buy_in_cap,
+            )
+        }
+
+        /// Tokens still available through buy-ins in the current period
+        #[ink(message)]
+        pub fn get_buy_in_remaining(&self) -> Balance {
+            let now = self.env().block_number() as u64;
+            if now >= self.buy_in_period_start + self.buy_in_period {
+                return self.buy_in_cap;
+            }
+            self.buy_in_cap.saturating_sub(self.buy_in_issued)
+        }
+
+        #[ink(message)]
+        pub fn get_observers(&self) -> Vec<H160> {
+            self.observers.get_or_default()
+        }
+
+        #[ink(message)]
+        pub fn is_observer(&self, account: H160) -> bool {
+            self.observers.get_or_default().contains(&account)
+        }
+
+        #[ink(message)]
+        pub fn get_participation(&self, member: H160) -> Participation {
+            if !self.members.contains(member) {
+                return Participation::default();
+            }
+            let (record, _) = self.settled_vote_record(member);
+            Participation {
+                eligible: record.voted.saturating_add(record.missed),
+                voted: record.voted,
+            }
+        }
+
+        /// Percentage of closed proposals the member voted on, `None` before any closed
+        #[ink(message)]
+        pub fn participation_rate(&self, member: H160) -> Option<u8> {
+            Self::participation_percent(self.get_participation(member))
+        }
+
+        /// Percentage of possible ballots cast across all closed proposals
+        #[ink(message)]
+        pub fn get_turnout(&self) -> Option<u8> {
+            Self::participation_percent(self.total_participation)
+        }
+
+        /// Proposal counts, turnout, treasury flows and membership in one call
+        #[ink(message)]
+        pub fn get_stats(&self) -> DaoStats {
+            DaoStats {
+                total_proposals: self.next_proposal_id.saturating_sub(1),
+                draft_proposals: self.draft_proposals,
+                active_proposals: self.active_proposal_count,
+                passed_proposals: self.passed_proposals,
+                rejected_proposals: self.rejected_proposals,
+                expired_proposals: self.expired_proposals,
+                cancelled_proposals: self.cancelled_proposals,
+                vetoed_proposals: self.vetoed_proposals,
+                average_turnout: self.get_turnout(),
+                treasury_inflow: self.treasury_inflow,
+                treasury_outflow: self.treasury_outflow,
+                member_count: self.member_count,
+                total_supply: self.total_supply,
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_reputation(&self, member: H160) -> u32 {
+            self.reputation.get(member).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_vote_weight_source(&self) -> VoteWeightSource {
+            self.vote_weight_source
+        }
+
+        /// Tier of a member, `None` for non-members
+        #[ink(message)]
+        pub fn get_tier(&self, account: H160) -> Option<Tier> {
+            if !self.members.contains(account) {
+                return None;
+            }
+            Some(self.member_tiers.get(account).unwrap_or_default())
+        }
+
+        #[ink(message)]
+        pub fn get_tier_config(&self, tier: Tier) -> TierConfig {
+            self.tier_configs.get(tier).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_resignation_policy(&self) -> ResignationPolicy {
+            self.resignation_policy
+        }
+
+        /// Name, description and `dao_uri` of the DAO as set by governance
+        #[ink(message)]
+        pub fn dao_metadata(&self) -> DaoMetadata {
+            self.metadata.get_or_default()
+        }
+
+        /// Nonce the next meta-transaction of `signer` must carry
+        #[ink(message)]
+        pub fn get_nonce(&self, signer: H160) -> u64 {
+            self.nonces.get(signer).unwrap_or(0)
+        }
+
+        /// Hash a member signs to authorize `action`: keccak256 of the SCALE encoding of
+        /// `(META_TX_DOMAIN, chain_id, dao address, signer, nonce, action)`
+        #[ink(message)]
+        pub fn meta_tx_hash(&self, signer: H160, nonce: u64, action: SignedAction) -> [u8; 32] {
+            self.env().hash_encoded::<Keccak256, _>(&(
+                META_TX_DOMAIN,
+                self.chain_id,
+                self.env().address(),
+                signer,
+                nonce,
+                action,
+            ))
+        }
+
+        /// Listener contracts notified of governance events
+        #[ink(message)]
+        pub fn get_hooks(&self) -> Vec<Hook> {
+            self.hooks.get_or_default()
+        }
+
+        /// Contract executing passed proposals in place of members, if any
+        #[ink(message)]
+        pub fn get_executor(&self) -> Option<H160> {
+            self.executor
+        }
+
+        #[ink(message)]
+        pub fn get_randomness_oracle(&self) -> Option<H160> {
+            self.randomness_oracle
+        }
+
+        /// Members drawn to vote on a proposal, `None` if every member votes
+        #[ink(message)]
+        pub fn get_jury(&self, proposal_id: u32) -> Option<Vec<H160>> {
+            self.juries.get(proposal_id)
+        }
+
+        /// Jurors drawn for each proposal of a kind, 0 if every member votes
+        #[ink(message)]
+        pub fn get_jury_size(&self, kind: u8) -> u32 {
+            self.jury_sizes.get(kind).unwrap_or(0)
+        }
+
+        /// Randomness an executed draw proposal still waits for
+        #[ink(message)]
+        pub fn get_pending_draw(&self, proposal_id: u32) -> Option<PendingDraw> {
+            self.pending_draws.get(proposal_id)
+        }
+
+        /// Token voting power is read from under `VoteWeightSource::External`, if any
+        #[ink(message)]
+        pub fn get_external_token(&self) -> Option<ExternalToken> {
+            self.external_token.clone()
+        }
+
+        /// External PSP22 token wrapped into governance tokens, if any
+        #[ink(message)]
+        pub fn get_wrapped_token(&self) -> Option<H160> {
+            self.wrapped_token
+        }
+
+        /// External tokens an account wrapped and hasn't unwrapped yet
+        #[ink(message)]
+        pub fn get_wrapped_deposit(&self, account: H160) -> Balance {
+            self.wrapped_deposits.get(account).unwrap_or(0)
+        }
+
+        /// Merkle root of the current airdrop round, `None` while airdrops are closed
+        #[ink(message)]
+        pub fn get_airdrop_root(&self) -> Option<[u8; 32]> {
+            self.airdrop_root
+        }
+
+        #[ink(message)]
+        pub fn is_airdrop_claimed(&self, account: H160) -> bool {
+            self.airdrop_claims.contains((self.airdrop_round, account))
+        }
+
+        /// Next emission amount, epoch length, decay percent and the block the
+        /// next emission is due at
+        #[ink(message)]
+        pub fn get_emission_schedule(&self) -> (Balance, u64, u8, u64) {
+            (
+                self.emission_amount,
+                self.emission_epoch_length,
+                self.emission_decay_percent,
+                self.next_emission_block,
+            )
+        }
+
+        /// Governance tokens owned by the DAO itself
+        #[ink(message)]
+        pub fn get_treasury_tokens(&self) -> Balance {
+            self.treasury_tokens
+        }
+
+        #[ink(message)]
+        pub fn get_vesting_schedule(&self, member: H160) -> Option<VestingSchedule> {
+            self.vesting.get(member)
+        }
+
+        /// Tokens the member could claim right now with `claim_vested`
+        #[ink(message)]
+        pub fn get_claimable_vested(&self, member: H160) -> Balance {
+            self.vesting
+                .get(member)
+                .map(|schedule| self.vested(&schedule) - schedule.claimed)
+                .unwrap_or(0)
+        }
+
+        /// Token slashes of an account, oldest first
+        #[ink(message)]
+        pub fn get_slash_history(&self, member: H160) -> Vec<SlashRecord> {
+            self.slashes.get(member).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_staked(&self, member: H160) -> Balance {
+            self.staked.get(member).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_unbonding(&self, member: H160) -> Option<Unbonding> {
+            self.unbonding.get(member)
+        }
+
+        #[ink(message)]
+        pub fn get_unbonding_period(&self) -> u64 {
+            self.unbonding_period
+        }
+
+        /// Pause proposal creation, voting and execution. Guardians and council members pause at once, other
+        /// members once `pause_quorum` of them called it; only an `Unpause` proposal resumes.
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<()> {
+            let caller: H160 = self.env().caller();
+
+            if !self.members.contains(caller) {
+                return Err(Error::NotMember);
+            }
+            if self.paused {
+                return Err(Error::Paused);
+            }
+
+            let council_member = self.sitting_council().contains(&caller);
+            if council_member {
+                Self::env().emit_event(CouncilActionTaken {
+                    proposal_id: None,
+                    member: caller,
+                    action: None,
+                    approvals: 1,
+                    applied: true,
+                });
+            } else if self.ensure_role(caller, Role::Guardian).is_err() {
+                if self.pause_quorum == 0 {
+                    return Err(Error::MissingRole);
+                }
+                if self.pause_calls.contains((self.pause_round, caller)) {
+                    return Err(Error::AlreadyVoted);
+                }
+                self.pause_calls.insert((self.pause_round, caller), &());
+                self.pause_call_count += 1;
+
+                Self::env().emit_event(PauseRequested {
+                    member: caller,
+                    calls: self.pause_call_count,
+                });
+
+                if self.pause_call_count < self.pause_quorum {
+                    return Ok(());
+                }
+            }
+
+            self.paused = true;
+
+            Self::env().emit_event(DaoPaused { by: caller });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Code version, starting at 1 and bumped by every executed upgrade
+        #[ink(message)]
+        pub fn get_code_version(&self) -> u32 {
+            self.code_version
+        }
+
+        #[ink(message)]
+        pub fn get_upgrade_timelock(&self) -> u64 {
+            self.upgrade_timelock
+        }
+
+        /// Kind of a proposal type, the key `ConfigureProposalType` sets rules for
+        #[ink(message)]
+        pub fn proposal_kind(&self, proposal_type: ProposalType) -> u8 {
+            Self::kind_of(&proposal_type)
+        }
+
+        /// Whether a `FreezeMember` proposal locked out the account
+        #[ink(message)]
+        pub fn is_frozen(&self, account: H160) -> bool {
+            self.frozen.contains(account)
+        }
+
+        /// Open dispute proposal contesting a proposal, if any
+        #[ink(message)]
+        pub fn get_dispute(&self, proposal_id: u32) -> Option<u32> {
+            self.disputes.get(proposal_id)
+        }
+
+        #[ink(message)]
+        pub fn get_dispute_bond(&self) -> Balance {
+            self.dispute_bond
+        }
+
+        #[ink(message)]
+        pub fn get_arbiter(&self) -> Option<H160> {
+            self.arbiter
+        }
+
+        #[ink(message)]
+        pub fn get_rate_limit(&self) -> RateLimit {
+            self.rate_limit.clone()
+        }
+
+        /// Deposit the next proposal of `author` with the given type requires, including
+        /// any rate limit surcharge
+        #[ink(message)]
+        pub fn required_deposit(&self, author: H160, proposal_type: ProposalType) -> Balance {
+            self.type_config(&proposal_type)
+                .deposit
+                .saturating_add(self.rate_limit_surcharge(author))
+        }
+
+        /// Rules proposals of a kind are decided by
+        #[ink(message)]
+        pub fn get_type_config(&self, kind: u8) -> TypeConfig {
+            self.kind_config(kind)
+        }
+
+        /// First block a passed upgrade proposal may be executed at
+        #[ink(message)]
+        pub fn get_timelock(&self, proposal_id: u32) -> Option<u64> {
+            self.timelocks.get(proposal_id)
+        }
+
+        /// Whether governance tokens are non-transferable, fixed at construction
+        #[ink(message)]
+        pub fn is_soulbound(&self) -> bool {
+            self.soulbound
+        }
+
+        #[ink(message)]
+        pub fn get_member_only_transfers(&self) -> bool {
+            self.member_only_transfers
+        }
+
+        #[ink(message)]
+        pub fn get_kick_policy(&self) -> KickPolicy {
+            self.kick_policy
+        }
+
+        /// Tokens held aside for a removed member until they rejoin
+        #[ink(message)]
+        pub fn get_escrowed_tokens(&self, account: H160) -> Balance {
+            self.escrowed_tokens.get(account).unwrap_or(0)
+        }
+
+        /// Block the membership lapses at, `None` while memberships don't expire
+        #[ink(message)]
+        pub fn get_membership_expiry(&self, member: H160) -> Option<u64> {
+            if self.membership_term == 0 {
+                return None;
+            }
+            self.membership_expiry.get(member)
+        }
+
+        #[ink(message)]
+        pub fn get_lapsed_members(&self) -> Vec<H160> {
+            self.lapsed_members.get_or_default()
+        }
+
+        /// Membership term in blocks and the fee to renew it
+        #[ink(message)]
+        pub fn get_membership_term(&self) -> (u64, Balance) {
+            (self.membership_term, self.renewal_fee)
+        }
+
+        /// Block the member joined at, `None` for non-members
+        #[ink(message)]
+        pub fn member_since(&self, member: H160) -> Option<u64> {
+            self.members.get(member).map(|info| info.joined_at)
+        }
+
+        #[ink(message)]
+        pub fn get_member_profile(&self, member: H160) -> Option<MemberProfile> {
+            self.profiles.get(member)
+        }
+
+        /// Role of a member, `None` for non-members
+        #[ink(message)]
+        pub fn get_role(&self, account: H160) -> Option<Role> {
+            if !self.members.contains(account) {
+                return None;
+            }
+            Some(self.roles.get(account).unwrap_or_default())
+        }
+
+        #[ink(message)]
+        pub fn add_blocks(&self) -> u32 {
+            self.env().block_number()
+        }
+
+        /// Weight of a member's vote, external token balances are read at `snapshot_block`
+        fn vote_weight_of(&self, member: H160, snapshot_block: u64) -> u32 {
+            match self.vote_weight_source {
+                VoteWeightSource::Tier => self.tier_config_of(member).vote_weight,
+                VoteWeightSource::Reputation => self.reputation.get(member).unwrap_or(0).max(1),
+                VoteWeightSource::Tokens => self.token_vote_weight(member, snapshot_block),
+                VoteWeightSource::External => self.external_vote_weight(member, snapshot_block),
+                VoteWeightSource::Blended { reputation_percent } => {
+                    let reputation = self.reputation.get(member).unwrap_or(0) as u64;
+                    let tokens = self.token_vote_weight(member, snapshot_block) as u64;
+                    let weight = (reputation * reputation_percent as u64
+                        + tokens * (100 - reputation_percent as u64))
+                        / 100;
+                    u32::try_from(weight).unwrap_or(u32::MAX).max(1)
+                }
+            }
+        }
+
+        /// Weight of a vote cast now on a proposal whose kind rewards early votes, the bonus
+        /// shrinking linearly to nothing at the deadline
+        fn decayed_weight(&self, weight: u32, proposal: &Proposal) -> u32 {
+            let Some(bonus) = self.vote_decays.get(Self::kind_of(&proposal.proposal_type)) else {
+                return weight;
+            };
+            let period = self
+                .type_config(&proposal.proposal_type)
+                .voting_period
+                .max(1);
+            let remaining = proposal
+                .voting_deadline
+                .saturating_sub(self.env().block_number() as u64)
+                .min(period);
+            let bonus = weight as u64 * bonus as u64 * remaining / (100 * period);
+            u32::try_from(weight as u64 + bonus).unwrap_or(u32::MAX)
+        }
+
+        /// Stake voting through a member at the end of `snapshot_block`, optionally with
+        /// vested tokens unclaimed by then. Stake moved or delegated later can't vote again.
+        fn token_vote_weight(&self, member: H160, snapshot_block: u64) -> u32 {
+            let mut tokens = self.voting_power_at(member, snapshot_block);
+            if self.vested_vote_power {
+                if let Some(schedule) = self.vesting.get(member) {
+                    let unclaimed =
+                        Self::vested_at(&schedule, snapshot_block).saturating_sub(schedule.claimed);
+                    tokens = tokens.saturating_add(unclaimed);
+                }
+            }
+            u32::try_from(tokens).unwrap_or(u32::MAX)
+        }
+
+        /// Votes of a member in the external token. Plain PSP22 tokens only report current
+        /// balances, so there the weight is read when the vote is cast.
+        fn external_vote_weight(&self, member: H160, snapshot_block: u64) -> u32 {
+            let Some(external) = &self.external_token else {
+                return 0;
+            };
+            // Live balances could be moved and voted with again, only snapshots count
+            if !external.votes_style {
+                return 0;
+            }
+            let token: ink::contract_ref_from_path!(PSP22Votes) = external.token.into();
+            let tokens = token.get_past_votes(member, snapshot_block);
+            u32::try_from(tokens / external.tokens_per_vote).unwrap_or(u32::MAX)
+        }
+
+        /// Token balance neither staked nor unbonding
+        fn unlocked_tokens(&self, member: H160) -> Balance {
+            let locked = self.staked.get(member).unwrap_or(0)
+                + self.unbonding.get(member).map(|u| u.amount).unwrap_or(0);
+            self.member_tokens
+                .get(member)
+                .unwrap_or(0)
+                .saturating_sub(locked)
+        }
+
+        /// Tokens of a schedule unlocked at the current block, including claimed ones
+        fn vested(&self, schedule: &VestingSchedule) -> Balance {
+            Self::vested_at(schedule, self.env().block_number() as u64)
+        }
+
+        /// Tokens of a schedule unlocked at `block`, including claimed ones
+        fn vested_at(schedule: &VestingSchedule, block: u64) -> Balance {
+            let elapsed = block.saturating_sub(schedule.start_block);
+            if elapsed < schedule.cliff {
+                return 0;
+            }
+            if elapsed >= schedule.duration {
+                return schedule.total;
+            }
+            schedule.total.saturating_mul(elapsed as Balance) / schedule.duration as Balance
+        }
+
+        /// Count a closed proposal towards every member's participation, each member's
+        /// own votes are booked by `settle_participation`
+        fn record_participation(&mut self, proposal: &Proposal) {
+            self.closed_proposals = self.closed_proposals.saturating_add(1);
+            self.total_participation.eligible = self
+                .total_participation
+                .eligible
+                .saturating_add(self.member_count);
+            self.total_participation.voted = self
+                .total_participation
+                .voted
+                .saturating_add(proposal.voter_count);
+            self.award_badge(proposal.author, Badge::FirstProposal);
+        }
+
+        /// A member's vote record with ballots on proposals closed since booked, and the
+        /// rebates those ballots earned
+        fn settled_vote_record(&self, member: H160) -> (VoteRecord, Balance) {
+            let mut record = self.vote_records.get(member).unwrap_or_default();
+            let mut settled = 0u32;
+            let mut rebate: Balance = 0;
+            record.open.retain(|id| {
+                let closed = self
+                    .proposals
+                    .get(id)
+                    .is_none_or(|proposal| proposal.status != ProposalStatus::Active);
+                if closed {
+                    settled += 1;
+                    rebate = rebate.saturating_add(self.proposal_rebates.get(id).unwrap_or(0));
+                }
+                !closed
+            });
+
+            record.voted = record.voted.saturating_add(settled);
+            let missed = self
+                .closed_proposals
+                .saturating_sub(record.joined_at)
+                .saturating_sub(record.voted);
+            // Misses since the last settlement break the streak, their order isn't kept
+            record.streak = if missed > record.missed {
+                settled
+            } else {
+                record.streak.saturating_add(settled)
+            };
+            record.missed = missed;
+            (record, rebate)
+        }
+
+        /// Book a member's ballots on proposals closed since their last settlement, crediting
+        /// the rebates the treasury still covers and awarding badges
+        fn settle_participation(&mut self, member: H160) {
+            let (record, rebate) = self.settled_vote_record(member);
+            if rebate > 0 && self.reserve_funds(None, rebate).is_ok() {
+                let rebates = self.rebates.get(member).unwrap_or(0);
+                self.rebates.insert(member, &rebates.saturating_add(rebate));
+            }
+            if record.voted >= BADGE_VOTES {
+                self.award_badge(member, Badge::Voter);
+            }
+            if record.streak >= BADGE_STREAK {
+                self.award_badge(member, Badge::TurnoutStreak);
+            }
+            self.vote_records.insert(member, &record);
+        }
+
+        /// Give a member a badge they don't hold yet, non-members earn nothing
+        fn award_badge(&mut self, member: H160, badge: Badge) {
+            if !self.members.contains(member) {
+                return;
+            }
+            let mut badges = self.badges.get(member).unwrap_or_default();
+            if badges.contains(&badge) {
+                return;
+            }
+            badges.push(badge);
+            self.badges.insert(member, &badges);
+
+            Self::env().emit_event(BadgeAwarded { member, badge });
+        }
+
+        fn participation_percent(participation: Participation) -> Option<u8> {
+            if participation.eligible == 0 {
+                return None;
+            }
+            Some((participation.voted as u64 * 100 / participation.eligible as u64) as u8)
+        }
+
+        /// Reward participation of a member up to `MAX_REPUTATION_PER_EPOCH` per voting
+        /// period, non-members earn nothing
+        fn add_reputation(&mut self, member: H160, amount: u32) {
+            if !self.members.contains(member) {
+                return;
+            }
+            let epoch = (self.env().block_number() as u64)
+                .checked_div(self.voting_period)
+                .unwrap_or(0);
+            let earned = match self.reputation_earned.get(member) {
+                Some((counted, earned)) if counted == epoch => earned,
+                _ => 0,
+            };
+            let amount = amount.min(MAX_REPUTATION_PER_EPOCH.saturating_sub(earned));
+            if amount == 0 {
+                return;
+            }
+            self.reputation_earned
+                .insert(member, &(epoch, earned + amount));
+            let reputation = self
+                .reputation
+                .get(member)
+                .unwrap_or(0)
+                .saturating_add(amount);
+            self.set_reputation(member, reputation);
+
+            Self::env().emit_event(ReputationChanged { member, reputation });
+        }
+
+        /// Set a member's reputation, keeping the totals the voting power is summed from
+        fn set_reputation(&mut self, member: H160, reputation: u32) {
+            let previous = self.reputation.get(member).unwrap_or(0);
+            self.total_reputation = self
+                .total_reputation
+                .saturating_sub(previous as u64)
+                .saturating_add(reputation as u64);
+            if previous == 0 && reputation > 0 {
+                self.reputation_holders = self.reputation_holders.saturating_add(1);
+            } else if previous > 0 && reputation == 0 {
+                self.reputation_holders = self.reputation_holders.saturating_sub(1);
+            }
+            if reputation == 0 {
+                self.reputation.remove(member);
+            } else {
+                self.reputation.insert(member, &reputation);
+            }
+        }
+
+        fn tier_config_of(&self, member: H160) -> TierConfig {
+            let tier = self.member_tiers.get(member).unwrap_or_default();
+            self.tier_configs.get(tier).unwrap_or_default()
+        }
+
+        fn is_membership_expired(&self, member: H160) -> bool {
+            match self.get_membership_expiry(member) {
+                Some(expires_at) => expires_at <= self.env().block_number() as u64,
+                None => false,
+            }
+        }
+
+        /// Require `account` to be a member holding at least `role`
+        fn ensure_role(&self, account: H160, role: Role) -> Result<()> {
+            match self.get_role(account) {
+                None => Err(Error::NotMember),
+                Some(held) if held < role => Err(Error::MissingRole),
+                Some(_) => Ok(()),
+            }
+        }
+
+        /// Record a holder's current balance, must run after their balance changes
+        fn checkpoint_balance(&mut self, holder: H160) {
+            let mut checkpoints = self.balance_checkpoints.get(holder).unwrap_or_default();
+            self.record_checkpoint(
+                &mut checkpoints,
+                self.member_tokens.get(holder).unwrap_or(0),
+            );
+            self.balance_checkpoints.insert(holder, &checkpoints);
+        }
+
+        /// Append the amount at the current block, replacing an entry of the same block
+        fn record_checkpoint(&self, checkpoints: &mut Vec<Checkpoint>, amount: Balance) {
+            let checkpoint = Checkpoint {
+                block: self.env().block_number() as u64,
+                amount,
+            };
+            match checkpoints.last_mut() {
+                Some(last) if last.block == checkpoint.block => *last = checkpoint,
+                _ => checkpoints.push(checkpoint),
+            }
+        }
+
+        /// Amount of the latest checkpoint at or before `block`
+        fn checkpoint_at(checkpoints: &[Checkpoint], block: u64) -> Balance {
+            let index = checkpoints.partition_point(|c| c.block <= block);
+            if index == 0 {
+                return 0;
+            }
+            checkpoints[index - 1].amount
+        }
+
+        fn delegate_of(&self, member: H160) -> H160 {
+            self.delegates.get(member).unwrap_or(member)
+        }
+
+        fn resolved_delegate(&self, member: H160) -> H160 {
+            *self.delegation_chain(member).last().unwrap_or(&member)
+        }
+
+        /// Accounts a member's stake passes through, the member excluded and the
+        /// resolved delegate last. `delegate` keeps chains acyclic and within
+        /// `MAX_DELEGATION_HOPS`, the bound only caps the walk.
+        fn delegation_chain(&self, member: H160) -> Vec<H160> {
+            let mut chain = Vec::new();
+            let mut current = member;
+            while chain.len() < MAX_DELEGATION_HOPS {
+                let Some(next) = self.delegates.get(current) else {
+                    break;
+                };
+                chain.push(next);
+                current = next;
+            }
+            chain
+        }
+
+        /// Point a member's delegation at `to`, moving their stake and all stake
+        /// delegated to them from the old chain onto the new one
+        fn redelegate(&mut self, member: H160, to: H160) {
+            let behind = self.delegated.get(member).unwrap_or_default();
+            let routed = self
+                .staked
+                .get(member)
+                .unwrap_or(0)
+                .saturating_add(behind.stake);
+
+            let old_chain = self.delegation_chain(member);
+            self.route_delegation(&old_chain, &behind, routed, false);
+            self.change_voting_power(*old_chain.last().unwrap_or(&member), 0, routed);
+
+            if to == member {
+                self.delegates.remove(member);
+            } else {
+                self.delegates.insert(member, &to);
+            }
+
+            let new_chain = self.delegation_chain(member);
+            self.route_delegation(&new_chain, &behind, routed, true);
+            self.change_voting_power(*new_chain.last().unwrap_or(&member), routed, 0);
+        }
+
+        /// Add or take a delegator, the delegators behind it and the stake they route
+        /// at every account down `chain`
+        fn route_delegation(
+            &mut self,
+            chain: &[H160],
+            behind: &DelegatedStake,
+            stake: Balance,
+            adding: bool,
+        ) {
+            for (hops, account) in chain.iter().enumerate() {
+                let mut delegated = self.delegated.get(account).unwrap_or_default();
+                // The delegator sits `hops + 1` away, the ones behind it further still
+                let counts = core::iter::once(1).chain(behind.depths.iter().copied());
+                let farthest = hops + 1 + behind.depths.len();
+                if delegated.depths.len() < farthest {
+                    delegated.depths.resize(farthest, 0);
+                }
+                for (offset, count) in counts.enumerate() {
+                    let depth = &mut delegated.depths[hops + offset];
+                    *depth = if adding {
+                        depth.saturating_add(count)
+                    } else {
+                        depth.saturating_sub(count)
+                    };
+                }
+                while delegated.depths.last() == Some(&0) {
+                    delegated.depths.pop();
+                }
+                delegated.stake = if adding {
+                    delegated.stake.saturating_add(stake)
+                } else {
+                    delegated.stake.saturating_sub(stake)
+                };
+
+                if delegated.depths.is_empty() {
+                    self.delegated.remove(account);
+                } else {
+                    self.delegated.insert(account, &delegated);
+                }
+            }
+        }
+
+        /// Set a member's stake, moving the difference in voting power down their
+        /// delegation chain to its end
+        fn set_stake(&mut self, member: H160, staked: Balance) {
+            let previous = self.staked.get(member).unwrap_or(0);
+            if staked == 0 {
+                self.staked.remove(member);
+            } else {
+                self.staked.insert(member, &staked);
+            }
+            self.total_staked = self
+                .total_staked
+                .saturating_sub(previous)
+                .saturating_add(staked);
+            let added = staked.saturating_sub(previous);
+            let removed = previous.saturating_sub(staked);
+            let chain = self.delegation_chain(member);
+            for account in &chain {
+                let mut delegated = self.delegated.get(account).unwrap_or_default();
+                delegated.stake = delegated
+                    .stake
+                    .saturating_add(added)
+                    .saturating_sub(removed);
+                self.delegated.insert(account, &delegated);
+            }
+            self.change_voting_power(*chain.last().unwrap_or(&member), added, removed);
+        }
+
+        fn change_voting_power(&mut self, account: H160, added: Balance, removed: Balance) {
+            if added == removed {
+                return;
+            }
+            let power = self
+                .voting_power
+                .get(account)
+                .unwrap_or(0)
+                .saturating_add(added)
+                .saturating_sub(removed);
+            self.voting_power.insert(account, &power);
+
+            let mut checkpoints = self
+                .voting_power_checkpoints
+                .get(account)
+                .unwrap_or_default();
+            self.record_checkpoint(&mut checkpoints, power);
+            self.voting_power_checkpoints.insert(account, &checkpoints);
+        }
+
+        /// Dividends earned by a holder's balance since they were last settled
+        fn unsettled_dividends(&self, holder: H160) -> Balance {
+            let checkpoint = self.dividend_checkpoints.get(holder).unwrap_or(0);
+            self.member_tokens
+                .get(holder)
+                .unwrap_or(0)
+                .saturating_mul(self.dividend_per_token - checkpoint)
+                / DIVIDEND_PRECISION
+        }
+
+        /// Book a holder's earned dividends, must run before their balance changes
+        fn settle_dividends(&mut self, holder: H160) {
+            let earned = self.unsettled_dividends(holder);
+            if earned > 0 {
+                let dividends = self.dividends.get(holder).unwrap_or(0);
+                self.dividends.insert(holder, &(dividends + earned));
+            }
+            self.dividend_checkpoints
+                .insert(holder, &self.dividend_per_token);
+        }
+
+        fn add_member(&mut self, member: H160, tokens: Balance) -> Result<()> {
+            if self.member_count >= self.max_members {
+                return Err(Error::MemberLimitReached);
+            }
+
+            // Tokens escrowed on an earlier removal come back on rejoining
+            let tokens = tokens
+                .checked_add(self.escrowed_tokens.get(member).unwrap_or(0))
+                .ok_or(Error::Overflow)?;
+            // Shares held before joining, through wrapping or transfers, are kept
+            let balance = self
+                .member_tokens
+                .get(member)
+                .unwrap_or(0)
+                .checked_add(tokens)
+                .ok_or(Error::Overflow)?;
+            let total_supply = self
+                .total_supply
+                .checked_add(tokens)
+                .ok_or(Error::Overflow)?;
+            self.escrowed_tokens.remove(member);
+            self.kicked.remove(member);
+            self.settle_dividends(member);
+            let mut observers = self.observers.get_or_default();
+            if observers.contains(&member) {
+                observers.retain(|o| *o != member);
+                self.observers.set(&observers);
+            }
+            self.insert_member(member);
+            // Proposals closed before joining don't count against participation
+            self.vote_records.insert(
+                member,
+                &VoteRecord {
+                    joined_at: self.closed_proposals,
+                    ..Default::default()
+                },
+            );
+            self.member_tokens.insert(member, &balance);
+            self.checkpoint_balance(member);
+            self.total_supply = total_supply;
+            if self.membership_term > 0 {
+                self.membership_expiry.insert(
+                    member,
+                    &(self.env().block_number() as u64 + self.membership_term),
+                );
+            }
+            self.mint_membership_nft(member);
+
+            Self::env().emit_event(MemberAdded { member, tokens });
+            self.notify_hooks(HookEvent::MemberJoined(member));
+            Self::env().emit_event(Transfer {
+                from: None,
+                to: Some(member),
+                value: tokens,
+            });
+
+            Ok(())
+        }
+
+        /// Append an account to the member enumeration
+        fn insert_member(&mut self, member: H160) {
+            let tier = self.member_tiers.get(member).unwrap_or_default();
+            self.change_tier_count(tier, true);
+            let index = self.member_count;
+            let info = MemberInfo {
+                index,
+                joined_at: self.env().block_number() as u64,
+            };
+            self.members.insert(member, &info);
+            self.member_positions.insert(index, &member);
+            self.member_count += 1;
+        }
+
+        fn change_tier_count(&mut self, tier: Tier, joined: bool) {
+            let count = self.tier_counts.get(tier).unwrap_or(0);
+            let count = if joined {
+                count.saturating_add(1)
+            } else {
+                count.saturating_sub(1)
+            };
+            self.tier_counts.insert(tier, &count);
+        }
+
+        /// Take an account out of the member enumeration, the last member fills its position
+        fn delete_member(&mut self, member: H160) {
+            let Some(info) = self.members.take(member) else {
+                return;
+            };
+            let tier = self.member_tiers.get(member).unwrap_or_default();
+            self.change_tier_count(tier, false);
+
+            let last = self.member_count - 1;
+            if info.index != last {
+                if let Some(moved) = self.member_positions.get(last) {
+                    self.member_positions.insert(info.index, &moved);
+                    if let Some(mut moved_info) = self.members.get(moved) {
+                        moved_info.index = info.index;
+                        self.members.insert(moved, &moved_info);
+                    }
+                }
+            }
+            self.member_positions.remove(last);
+            self.member_count = last;
+        }
+
+        /// Add a proposal to the active enumeration
+        fn insert_active_proposal(
+            &mut self,
+            proposal_id: u32,
+            voting_deadline: u64,
+            voting_period: u64,
+        ) {
+            if self.active_proposal_indices.contains(proposal_id) {
+                return;
+            }
+            let index = self.active_proposal_count;
+            self.active_proposal_indices.insert(proposal_id, &index);
+            self.active_proposal_positions.insert(index, &proposal_id);
+            self.active_proposal_count += 1;
+
+            // Proposals sharing a voting period close in the order they opened
+            let (head, tail) = self
+                .deadline_queue_bounds
+                .get(voting_period)
+                .unwrap_or((0, 0));
+            let mut periods = self.queued_periods.get_or_default();
+            if !periods.contains(&voting_period) {
+                periods.push(voting_period);
+                self.queued_periods.set(&periods);
+            }
+            self.deadline_queues
+                .insert((voting_period, tail), &(proposal_id, voting_deadline));
+            self.deadline_queue_bounds
+                .insert(voting_period, &(head, tail + 1));
+        }
+
+        /// Changes to the DAO's code or controls, executed only after the timelock
+        fn is_sensitive(proposal_type: &ProposalType) -> bool {
+            match proposal_type {
+                ProposalType::AssignRole { role, .. } => *role >= Role::Guardian,
+                ProposalType::Upgrade(_)
+                | ProposalType::SetUpgradeTimelock(_)
+                | ProposalType::SetPauseQuorum(_)
+                | ProposalType::ElectCouncil(_)
+                | ProposalType::ConfigureCouncilTerms { .. }
+                | ProposalType::ConfigureProposalType { .. }
+                | ProposalType::XcmExecute(_)
+                | ProposalType::XcmSend { .. }
+                | ProposalType::SetRandomnessOracle(_)
+                | ProposalType::SetExecutor(_)
+                | ProposalType::ConfigureDisputes { .. } => true,
+                _ => false,
+            }
+        }
+
+        /// Decide an active proposal on the votes cast so far
+        fn close_voting_early(&mut self, mut proposal: Proposal) -> Result<()> {
+            if proposal.status != ProposalStatus::Active
+                || self.env().block_number() as u64 > proposal.voting_deadline
+            {
+                return Err(Error::ProposalExpired);
+            }
+
+            let total_votes = Self::total_votes(&proposal);
+            if total_votes == 0 {
+                return Err(Error::NoVotesCast);
+            }
+            let leading = match proposal.proposal_type {
+                ProposalType::MultipleChoice | ProposalType::BudgetVote { .. } => {
+                    *proposal.votes.iter().max().unwrap_or(&0)
+                }
+                // Seats are filled by whatever votes were cast
+                ProposalType::Election { .. } => total_votes,
+                _ => proposal.votes[0],
+            };
+            let pass_threshold = self.type_config(&proposal.proposal_type).pass_threshold;
+            let status = if Self::exceeds_threshold(pass_threshold, leading, total_votes) {
+                ProposalStatus::Passed
+            } else {
+                ProposalStatus::Rejected
+            };
+            Self::set_status(&mut proposal, status)?;
+            self.proposals.insert(proposal.id, &proposal);
+            self.on_voting_closed(&proposal);
+
+            Ok(())
+        }
+
+        /// Block proposal activity while paused, except for the proposals unpausing the DAO
+        fn ensure_not_paused(&self, proposal_type: &ProposalType) -> Result<()> {
+            if self.paused && *proposal_type != ProposalType::Unpause {
+                return Err(Error::Paused);
+            }
+            Ok(())
+        }
+
+        /// Hold every payout from the treasury while paused, so nothing leaves it before a
+        /// fix lands
+        fn ensure_outflows_open(&self) -> Result<()> {
+            if self.paused {
+                return Err(Error::Paused);
+            }
+            Ok(())
+        }
+
+        /// Check that `meta_tx` carries the signer's next nonce and their signature over
+        /// `action`, then consume the nonce
+        fn verify_meta_tx(&mut self, meta_tx: &MetaTx, action: &SignedAction) -> Result<()> {
+            if self.chain_id == 0 {
+                return Err(Error::ChainIdNotSet);
+            }
+            let nonce = self.get_nonce(meta_tx.signer);
+            if meta_tx.nonce != nonce {
+                return Err(Error::InvalidNonce);
+            }
+
+            let hash = self.meta_tx_hash(meta_tx.signer, nonce, action.clone());
+            let public_key = self
+                .env()
+                .ecdsa_recover(&meta_tx.signature, &hash)
+                .map_err(|_| Error::InvalidSignature)?;
+            let signer = self
+                .env()
+                .ecdsa_to_eth_address(&public_key)
+                .map_err(|_| Error::InvalidSignature)?;
+            if H160::from(signer) != meta_tx.signer {
+                return Err(Error::InvalidSignature);
+            }
+
+            self.nonces.insert(meta_tx.signer, &(nonce + 1));
+
+            Self::env().emit_event(MetaTxRelayed {
+                signer: meta_tx.signer,
+                relayer: self.env().caller(),
+                nonce,
+            });
+
+            Ok(())
+        }
+
+        /// Refuse proposals of non-members and of members whose tier has no proposal rights,
+        /// observers go through `create_draft_proposal`
+        fn ensure_can_propose(&self, author: H160) -> Result<()> {
+            if !self.members.contains(author) {
+                return Err(Error::NotMember);
+            }
+            if !self.tier_config_of(author).can_propose {
+                return Err(Error::ProposalRightsMissing);
+            }
+            Ok(())
+        }
+
+        /// Refuse actions of a member locked out by a `FreezeMember` proposal
+        fn ensure_not_frozen(&self, account: H160) -> Result<()> {
+            if self.frozen.contains(account) {
+                return Err(Error::AccountFrozen);
+            }
+            Ok(())
+        }
+
+        /// Refuse fund-moving messages re-entered from an external call of this contract
+        fn ensure_not_entered(&self) -> Result<()> {
+            if self.entered.get().unwrap_or(false) {
+                return Err(Error::ReentrantCall);
+            }
+            Ok(())
+        }
+
+        /// Make an external call with the re-entrancy flag held. Callers finish their own
+        /// state changes first, the flag only guards the call itself.
+        fn guarded<T>(&mut self, call: impl FnOnce(&mut Self) -> T) -> Result<T> {
+            self.ensure_not_entered()?;
+            self.entered.set(&true);
+            let result = call(self);
+            self.entered.set(&false);
+            Ok(result)
+        }
+
+        /// Whether a queue entry is current, sponsored drafts leave an outdated one behind
+        fn awaits_deadline(&self, proposal_id: u32, deadline: u64) -> bool {
+            self.proposals.get(proposal_id).is_some_and(|proposal| {
+                proposal.status == ProposalStatus::Active && proposal.voting_deadline == deadline
+            })
+        }
+
+        /// Variant index of a proposal type, the key of its rules in `type_configs`
+        fn kind_of(proposal_type: &ProposalType) -> u8 {
+            ink::scale::Encode::encode(proposal_type)[0]
+        }
+
+        /// Rules of a proposal kind, the global ones unless governance configured the kind
+        fn kind_config(&self, kind: u8) -> TypeConfig {
+            self.type_configs.get(kind).unwrap_or(TypeConfig {
+                min_votes_required: self.min_votes_required,
+                pass_threshold: self.pass_threshold,
+                voting_period: self.voting_period,
+                deposit: self.proposal_deposit,
+                timelock: 0,
+            })
+        }
+
+        fn type_config(&self, proposal_type: &ProposalType) -> TypeConfig {
+            self.kind_config(Self::kind_of(proposal_type))
+        }
+
+        /// Drop closed proposals from the front of every deadline queue, forgetting emptied ones
+        fn advance_deadline_queues(&mut self) {
+            let mut periods = self.queued_periods.get_or_default();
+            let queued = periods.len();
+            periods.retain(|&period| self.advance_deadline_queue(period));
+            if periods.len() != queued {
+                self.queued_periods.set(&periods);
+            }
+        }
+
+        /// Drop closed proposals from the front of one queue, false once it is empty
+        fn advance_deadline_queue(&mut self, period: u64) -> bool {
+            let (mut head, tail) = self.deadline_queue_bounds.get(period).unwrap_or((0, 0));
+            while head < tail {
+                let open = self
+                    .deadline_queues
+                    .get((period, head))
+                    .is_some_and(|(id, deadline)| self.awaits_deadline(id, deadline));
+                if open {
+                    break;
+                }
+                self.deadline_queues.remove((period, head));
+                head += 1;
+            }
+            self.deadline_queue_bounds.insert(period, &(head, tail));
+            head < tail
+        }
+
+        /// Take a proposal out of the active enumeration, the last entry fills its position
+        fn remove_active_proposal(&mut self, proposal_id: u32) {
+            let Some(index) = self.active_proposal_indices.take(proposal_id) else {
+                return;
+            };
+
+            let last = self.active_proposal_count - 1;
+            if index != last {
+                if let Some(moved) = self.active_proposal_positions.get(last) {
+                    self.active_proposal_positions.insert(index, &moved);
+                    self.active_proposal_indices.insert(moved, &index);
+                }
+            }
+            self.active_proposal_positions.remove(last);
+            self.active_proposal_count = last;
+        }
+
+        /// Proposals that only change governance parameters, announced with `ConfigChanged`
+        fn is_config_change(proposal_type: &ProposalType) -> bool {
+            matches!(
+                proposal_type,
+                ProposalType::SetProposalDeposit(_)
+                    | ProposalType::SetMaxRequestPercent(_)
+                    | ProposalType::ConfigChange(_)
+                    | ProposalType::ConfigureProposalType { .. }
+                    | ProposalType::ConfigureTips { .. }
+                    | ProposalType::ConfigureVoteRebates { .. }
+                    | ProposalType::ConfigureMembership { .. }
+                    | ProposalType::SetKickPolicy(_)
+                    | ProposalType::SetJoinFee(_)
+                    | ProposalType::ConfigureBuyIn { .. }
+                    | ProposalType::SetVoteWeightSource(_)
+                    | ProposalType::SetMaxMembers(_)
+                    | ProposalType::SetResignationPolicy(_)
+                    | ProposalType::SetMemberOnlyTransfers(_)
+                    | ProposalType::ConfigureEmission { .. }
+                    | ProposalType::SetWrappedToken(_)
+                    | ProposalType::SetVestingVotePower(_)
+                    | ProposalType::SetUnbondingPeriod(_)
+                    | ProposalType::ConfigureTier { .. }
+                    | ProposalType::ConfigureMembershipTerm { .. }
+                    | ProposalType::SetUpgradeTimelock(_)
+                    | ProposalType::SetPauseQuorum(_)
+                    | ProposalType::SetRateLimit(_)
+                    | ProposalType::ConfigureDisputes { .. }
+                    | ProposalType::ConfigureJury { .. }
+                    | ProposalType::ConfigureVoteDecay { .. }
+                    | ProposalType::ConfigureCouncilTerms { .. }
+            )
+        }
+
+        /// Current rate limit epoch, `None` while rate limiting is off
+        fn rate_limit_epoch(&self) -> Option<u64> {
+            let length = self.rate_limit.epoch_length;
+            (length > 0).then(|| self.env().block_number() as u64 / length)
+        }
+
+        /// Proposals `author` created in the current rate limit epoch
+        fn proposals_this_epoch(&self, author: H160) -> u32 {
+            match (self.rate_limit_epoch(), self.proposal_counts.get(author)) {
+                (Some(epoch), Some((counted, count))) if counted == epoch => count,
+                _ => 0,
+            }
+        }
+
+        /// Extra deposit the next proposal of `author` owes for exceeding the allowance
+        fn rate_limit_surcharge(&self, author: H160) -> Balance {
+            if self.rate_limit_epoch().is_none() {
+                return 0;
+            }
+            // Surcharged proposals the author already made this epoch
+            let Some(excess) = self
+                .proposals_this_epoch(author)
+                .checked_sub(self.rate_limit.free_proposals)
+            else {
+                return 0;
+            };
+
+            // Stops growing once saturated, or right away without growth
+            let mut surcharge = self.rate_limit.surcharge;
+            for _ in 0..excess {
+                let grown =
+                    surcharge.saturating_mul(100 + self.rate_limit.growth_percent as Balance) / 100;
+                if grown == surcharge {
+                    break;
+                }
+                surcharge = grown;
+            }
+            surcharge
+        }
+
+        fn count_proposal(&mut self, author: H160) {
+            if let Some(epoch) = self.rate_limit_epoch() {
+                let count = self.proposals_this_epoch(author).saturating_add(1);
+                self.proposal_counts.insert(author, &(epoch, count));
+            }
+        }
+
+        /// Status of a proposal, `Expired` once an active one is past its deadline
+        fn current_status(&self, proposal: &Proposal) -> ProposalStatus {
+            if proposal.status == ProposalStatus::Active
+                && self.env().block_number() as u64 > proposal.voting_deadline
+            {
+                return self.deadline_outcome(proposal);
+            }
+            proposal.status
+        }
+
+        /// Status of an active proposal once its voting deadline passed: elections that
+        /// reached the quorum are decided, everything else expires
+        fn deadline_outcome(&self, proposal: &Proposal) -> ProposalStatus {
+            if matches!(proposal.proposal_type, ProposalType::Election { .. })
+                && Self::total_votes(proposal) >= self.quorum(proposal)
+            {
+                return ProposalStatus::Passed;
+            }
+            ProposalStatus::Expired
+        }
+
+        /// Record the quorum mode a proposal is decided under and, for a percentage of the
+        /// voting power, the power it is a percentage of
+        fn snapshot_voting_power(&mut self, proposal_id: u32) {
+            if let QuorumMode::PowerPercent(_) = self.quorum_mode {
+                self.quorum_modes.insert(proposal_id, &self.quorum_mode);
+                let power = self.total_voting_power();
+                self.voting_power_snapshots.insert(proposal_id, &power);
+            }
+        }
+
+        /// A multiple choice first round that reached the quorum but no majority
+        fn needs_runoff(&self, proposal: &Proposal) -> bool {
+            self.runoff_period > 0
+                && proposal.proposal_type == ProposalType::MultipleChoice
+                && proposal.options.len() > 2
+                && !self.runoff_rounds.contains(proposal.id)
+                && Self::total_votes(proposal) >= self.quorum(proposal)
+        }
+
+        /// Put the two leading options of an expired first round to a fresh vote
+        fn open_runoff(&mut self, first_round: &Proposal) {
+            // A stable sort keeps ballot order among equal counts
+            let mut ranking: Vec<u32> = (0..first_round.votes.len() as u32).collect();
+            ranking.sort_by(|a, b| {
+                first_round.votes[*b as usize].cmp(&first_round.votes[*a as usize])
+            });
+            let options = [ranking[0], ranking[1]];
+
+            let runoff_id = self.next_proposal_id;
+            let current_block = self.env().block_number() as u64;
+            let runoff = Proposal {
+                id: runoff_id,
+                name: first_round.name.clone(),
+                description: String::new(),
+                author: first_round.author,
+                proposal_type: ProposalType::MultipleChoice,
+                options: options
+                    .iter()
+                    .map(|option| first_round.options[*option as usize].clone())
+                    .collect(),
+                amount: None,
+                asset: None,
+                stream_duration: None,
+                milestones: Vec::new(),
+                envelope: None,
+                tranches: Vec::new(),
+                recipient: None,
+                votes: vec![0; 2],
+                voter_count: 0,
+                status: ProposalStatus::Active,
+                created_at: current_block,
+                voting_deadline: current_block + self.runoff_period,
+            };
+            self.proposals.insert(runoff_id, &runoff);
+            self.snapshot_voting_power(runoff_id);
+            let description = self
+                .proposal_descriptions
+                .get(first_round.id)
+                .unwrap_or_default();
+            let content_hash = self.env().hash_encoded::<Keccak256, _>(&ProposalContent {
+                name: runoff.name.clone(),
+                description: description.clone(),
+                proposal_type: ProposalType::MultipleChoice,
+                options: runoff.options.clone(),
+                amount: None,
+                terms: MoneyRequestTerms::default(),
+            });
+            self.proposal_descriptions.insert(runoff_id, &description);
+            self.content_hashes.insert(runoff_id, &content_hash);
+            // The jury of the first round decides the runoff too
+            if let Some(jury) = self.juries.get(first_round.id) {
+                self.juries.insert(runoff_id, &jury);
+            }
+            self.insert_active_proposal(runoff_id, runoff.voting_deadline, self.runoff_period);
+            self.next_proposal_id += 1;
+            self.runoffs.insert(first_round.id, &runoff_id);
+            self.runoff_rounds.insert(
+                runoff_id,
+                &Runoff {
+                    proposal_id: first_round.id,
+                    options,
+                },
+            );
+
+            Self::env().emit_event(ProposalCreated {
+                proposal_id: runoff_id,
+                author: runoff.author,
+                name: runoff.name,
+                proposal_type: ProposalType::MultipleChoice,
+                options_count: 2,
+                voting_deadline: runoff.voting_deadline,
+                recipient: None,
+            });
+            Self::env().emit_event(RunoffOpened {
+                proposal_id: first_round.id,
+                runoff_id,
+                options,
+                voting_deadline: runoff.voting_deadline,
+            });
+        }
+
+        fn summarize(&self, proposal: Proposal) -> ProposalSummary {
+            ProposalSummary {
+                id: proposal.id,
+                status: self.current_status(&proposal),
+                name: proposal.name,
+                proposal_type: proposal.proposal_type,
+                voting_deadline: proposal.voting_deadline,
+                turnout: proposal.voter_count,
+            }
+        }
+
+        /// Whether a proposal is active and its voting deadline hasn't passed
+        fn is_open_for_voting(&self, proposal_id: u32) -> bool {
+            self.proposals.get(proposal_id).is_some_and(|proposal| {
+                proposal.status == ProposalStatus::Active
+                    && self.env().block_number() as u64 <= proposal.voting_deadline
+            })
+        }
+
+        /// All members in enumeration order
+        fn member_list(&self) -> Vec<H160> {
+            (0..self.member_count)
+                .filter_map(|index| self.member_positions.get(index))
+                .collect()
+        }
+
+        /// Drop a member with their role and membership NFT. Wrapped external tokens
+        /// backing their shares become claimable through `claim_payout`; returns the
+        /// burned tokens not backed that way.
+        fn remove_member(&mut self, member: H160) -> Balance {
+            // Earned dividends stay claimable through `claim_payout`
+            self.settle_dividends(member);
+            self.dividend_checkpoints.remove(member);
+            if let Some(dividends) = self.dividends.take(member) {
+                let key: PayoutKey = (member, None);
+                let claimable = self.claimable.get(key).unwrap_or(0);
+                self.claimable.insert(key, &(claimable + dividends));
+            }
+            let burned = self.member_tokens.take(member).unwrap_or(0);
+            self.checkpoint_balance(member);
+            self.delete_member(member);
+            self.roles.remove(member);
+            let mut council = self.council.get_or_default();
+            if council.contains(&member) {
+                council.retain(|m| *m != member);
+                self.council.set(&council);
+            }
+            let mut candidates = self.council_candidates.get_or_default();
+            if candidates.contains(&member) {
+                candidates.retain(|c| *c != member);
+                self.council_candidates.set(&candidates);
+            }
+            self.vacate_committee_seats(member);
+            self.member_tiers.remove(member);
+            self.set_reputation(member, 0);
+            self.reputation_earned.remove(member);
+            self.vote_records.remove(member);
+            self.vesting.remove(member);
+            self.set_stake(member, 0);
+            self.redelegate(member, member);
+            self.unbonding.remove(member);
+            self.profiles.remove(member);
+            self.pending_transfers.remove(member);
+            self.frozen.remove(member);
+            self.membership_expiry.remove(member);
+            let mut lapsed = self.lapsed_members.get_or_default();
+            if lapsed.contains(&member) {
+                lapsed.retain(|m| *m != member);
+                self.lapsed_members.set(&lapsed);
+            }
+            self.total_supply -= burned;
+
+            let mut unwrapped = 0;
+            if let Some(token) = self.wrapped_token {
+                let deposit = self.wrapped_deposits.get(member).unwrap_or(0);
+                unwrapped = deposit.min(burned);
+                if unwrapped > 0 {
+                    // Already reserved when wrapped
+                    let key: PayoutKey = (member, Some(token));
+                    let claimable = self.claimable.get(key).unwrap_or(0);
+                    self.claimable.insert(key, &(claimable + unwrapped));
+                    self.wrapped_deposits.insert(member, &(deposit - unwrapped));
+                    self.wrapped_total -= unwrapped;
+                }
+            }
+
+            if let Some(token_id) = self.membership_nfts.take(member) {
+                self.membership_nft_owners.remove(token_id);
+
+                Self::env().emit_event(MembershipNftBurned { member, token_id });
+            }
+
+            Self::env().emit_event(MemberRemoved { member, burned });
+            self.notify_hooks(HookEvent::MemberLeft(member));
+            Self::env().emit_event(Transfer {
+                from: Some(member),
+                to: None,
+                value: burned,
+            });
+
+            burned - unwrapped
+        }
+
+        /// Mint `amount` tokens to the members pro-rata to their holdings.
+        /// Rounding dust is not minted.
+        fn redistribute_tokens(&mut self, amount: Balance) -> Result<()> {
+            let total_supply = self.total_supply;
+            if amount == 0 || total_supply == 0 {
+                return Ok(());
+            }
+
+            for member in self.member_list() {
+                self.settle_dividends(member);
+                let balance = self.member_tokens.get(member).unwrap_or(0);
+                let share = amount.saturating_mul(balance) / total_supply;
+                let new_balance = balance.checked_add(share).ok_or(Error::Overflow)?;
+                self.member_tokens.insert(member, &new_balance);
+                self.checkpoint_balance(member);
+                self.total_supply = self
+                    .total_supply
+                    .checked_add(share)
+                    .ok_or(Error::Overflow)?;
+            }
+
+            Ok(())
+        }
+
+        fn mint_membership_nft(&mut self, member: H160) {
+            let token_id = self.next_membership_nft_id;
+            self.membership_nfts.insert(member, &token_id);
+            self.membership_nft_owners.insert(token_id, &member);
+            self.next_membership_nft_id += 1;
+
+            Self::env().emit_event(MembershipNftMinted { member, token_id });
+        }
+
+        /// Record the vote of `voter`, who either sent the call or signed it
+        fn cast_vote(&mut self, voter: H160, proposal_id: u32, option: u32) -> Result<()> {
+            // Check if voter is a member
+            if !self.members.contains(voter) {
+                return Err(Error::NotMember);
+            }
+            self.ensure_not_frozen(voter)?;
+            if self.is_membership_expired(voter) {
+                return Err(Error::MembershipExpired);
+            }
+
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+
+            self.ensure_not_paused(&proposal.proposal_type)?;
+
+            // Check if member has altready voted
+            if self.ballots.contains((proposal_id, voter)) {
+                return Err(Error::AlreadyVoted);
+            }
+
+            if proposal.status == ProposalStatus::Draft {
+                return Err(Error::ProposalNotSponsored);
+            }
+            if self.arbiter.is_some() && matches!(proposal.proposal_type, ProposalType::Dispute(_))
+            {
+                return Err(Error::NotArbiter);
+            }
+
+            // Check if proposal is still active
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalExpired);
+            }
+
+            // Check if voting period has expired
+            if self.env().block_number() as u64 > proposal.voting_deadline {
+                Self::set_status(&mut proposal, ProposalStatus::Expired)?;
+                self.proposals.insert(proposal_id, &proposal);
+                return Err(Error::ProposalExpired);
+            }
+
+            // Only the drawn jury votes on kinds decided by sortition
+            if self.pending_draws.contains(proposal_id) {
+                return Err(Error::JuryNotDrawn);
+            }
+            if self
+                .juries
+                .get(proposal_id)
+                .is_some_and(|jury| !jury.contains(&voter))
+            {
+                return Err(Error::NotJuror);
+            }
+
+            // Validate option
+            if option as usize >= proposal.options.len() {
+                return Err(Error::InvalidOption);
+            }
+
+            // Cast vote, weighted by the member's tier
+            let weight =
+                self.decayed_weight(self.vote_weight_of(voter, proposal.created_at), &proposal);
+            let tally = &mut proposal.votes[option as usize];
+            *tally = tally.checked_add(weight).ok_or(Error::Overflow)?;
+            proposal.voter_count = proposal.voter_count.saturating_add(1);
+            self.ballots.insert(
+                (proposal_id, voter),
+                &(option, self.env().block_number() as u64),
+            );
+            let mut record = self.vote_records.get(voter).unwrap_or_default();
+            record.open.push(proposal_id);
+            self.vote_records.insert(voter, &record);
+
+            self.add_reputation(voter, REPUTATION_PER_VOTE);
+
+            // Voting counts as activity and extends the membership
+            if self.membership_term > 0 {
+                self.membership_expiry.insert(
+                    voter,
+                    &(self.env().block_number() as u64 + self.membership_term),
+                );
+            }
+
+            // Update proposal status if needed
+            self.update_proposal_status(&mut proposal)?;
+
+            self.proposals.insert(proposal_id, &proposal);
+
+            if proposal.status != ProposalStatus::Active {
+                self.on_voting_closed(&proposal);
+            }
+            self.settle_participation(voter);
+
+            Self::env().emit_event(VoteCast {
+                proposal_id,
+                voter,
+                option,
+                weight,
+                status: proposal.status,
+            });
+
+            Ok(())
+        }
+
+        /// Store a proposal of `author`, who either sent the call or signed it, opening it for
+        /// votes unless it starts as a draft. Any deposit is paid by, and refunded to, the sender.
+        #[allow(clippy::too_many_arguments)]
+        fn insert_proposal(
+            &mut self,
+            author: H160,
+            name: String,
+            description: String,
+            proposal_type: ProposalType,
+            options: Vec<String>,
+            amount: Option<Balance>,
+            terms: MoneyRequestTerms,
+            status: ProposalStatus,
+        ) -> Result<u32> {
+            self.ensure_not_paused(&proposal_type)?;
+
+            // Bound what a single proposal stores and what reading it back decodes
+            if name.len() > MAX_PROPOSAL_NAME_LEN {
+                return Err(Error::NameTooLong);
+            }
+            if description.len() > MAX_PROPOSAL_DESCRIPTION_LEN {
+                return Err(Error::DescriptionTooLong);
+            }
+            if options.len() > MAX_OPTIONS {
+                return Err(Error::TooManyOptions);
+            }
+            if options.iter().any(|option| option.len() > MAX_OPTION_LEN) {
+                return Err(Error::OptionTooLong);
+            }
+
+            self.ensure_not_frozen(author)?;
+
+            // Validate proposal based on type
+            match proposal_type {
+                ProposalType::MultipleChoice => {
+                    if options.is_empty() {
+                        return Err(Error::InvalidProposalType);
+                    }
+                }
+                ProposalType::BudgetVote { ref amounts } => {
+                    if amount.is_some() || options.len() < 2 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if amounts.len() != options.len() || amounts.contains(&Some(0)) {
+                        return Err(Error::InvalidBudgetVote);
+                    }
+                    for amount in amounts.iter().flatten() {
+                        self.ensure_within_request_cap(None, *amount)?;
+                    }
+                }
+                ProposalType::MoneyRequest => {
+                    if amount.is_none() || options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if let Some(asset) = terms.asset {
+                        if !self.registered_assets.get_or_default().contains(&asset) {
+                            return Err(Error::AssetNotRegistered);
+                        }
+                    }
+                    if terms.recipient == Some(H160::zero()) {
+                        return Err(Error::InvalidRecipient);
+                    }
+                    if terms.stream_duration == Some(0) {
+                        return Err(Error::InvalidStreamDuration);
+                    }
+                    if !terms.milestones.is_empty()
+                        && (terms.stream_duration.is_some()
+                            || terms.milestones.contains(&0)
+                            || terms.milestones.iter().sum::<Balance>() != amount.unwrap_or(0))
+                    {
+                        return Err(Error::InvalidMilestones);
+                    }
+                    if !terms.tranches.is_empty()
+                        && (terms.stream_duration.is_some()
+                            || !terms.milestones.is_empty()
+                            || terms.tranches.contains(&0)
+                            || terms.tranches.iter().sum::<Balance>() != amount.unwrap_or(0))
+                    {
+                        return Err(Error::InvalidTranches);
+                    }
+                    self.ensure_within_request_cap(terms.asset, amount.unwrap_or(0))?;
+                    if let Some(envelope) = &terms.envelope {
+                        let budget = self.budgets.get(envelope).ok_or(Error::BudgetNotFound)?;
+                        if budget.asset != terms.asset {
+                            return Err(Error::BudgetAssetMismatch);
+                        }
+                        if budget.allocated - budget.spent < amount.unwrap_or(0) {
+                            return Err(Error::BudgetExceeded);
+                        }
+                    }
+                }
+                ProposalType::RegisterAsset(asset) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if self.registered_assets.get_or_default().contains(&asset) {
+                        return Err(Error::AssetAlreadyRegistered);
+                    }
+                }
+                ProposalType::CancelStream(stream_id) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    let stream = self.streams.get(stream_id).ok_or(Error::StreamNotFound)?;
+                    if stream.cancelled_at.is_some() {
+                        return Err(Error::StreamAlreadyCancelled);
+                    }
+                }
+                ProposalType::CancelEscrow(escrow_id) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    let escrow = self.escrows.get(escrow_id).ok_or(Error::EscrowNotFound)?;
+                    if escrow.cancelled {
+                        return Err(Error::EscrowAlreadyCancelled);
+                    }
+                }
+                ProposalType::RecurringPayment {
+                    asset,
+                    amount,
+                    interval,
+                    end_block,
+                    ..
+                } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if amount == 0 || interval == 0 || end_block <= self.env().block_number() as u64
+                    {
+                        return Err(Error::InvalidRecurringPayment);
+                    }
+                    if let Some(asset) = asset {
+                        if !self.registered_assets.get_or_default().contains(&asset) {
+                            return Err(Error::AssetNotRegistered);
+                        }
+                    }
+                }
+                ProposalType::CancelRecurringPayment(payment_id) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if !self
+                        .active_recurring_payments
+                        .get_or_default()
+                        .contains(&payment_id)
+                    {
+                        return Err(Error::RecurringPaymentNotFound);
+                    }
+                }
+                ProposalType::SetProposalDeposit(_) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                }
+                ProposalType::GrantProgress(grant_id) => {
+                    if options.len() != 2 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    let grant = self.grants.get(grant_id).ok_or(Error::GrantNotFound)?;
+                    if grant.clawed_back || grant.released as usize >= grant.tranches.len() {
+                        return Err(Error::GrantClosed);
+                    }
+                    if grant.pending_vote.is_some() {
+                        return Err(Error::GrantVotePending);
+                    }
+                }
+                ProposalType::ConfigureVoteRebates { .. } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                }
+                ProposalType::AddMember(member) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if self.members.contains(member) {
+                        return Err(Error::AlreadyMember);
+                    }
+                    if self.member_count >= self.max_members {
+                        return Err(Error::MemberLimitReached);
+                    }
+                    if let Some(invitation) = self.invitations.get(member) {
+                        if invitation.expires_at > self.env().block_number() as u64 {
+                            return Err(Error::InvitationPending);
+                        }
+                    }
+                }
+                ProposalType::ConfigureMembership { endorsements, .. } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if endorsements == 0 {
+                        return Err(Error::InvalidMembershipConfig);
+                    }
+                }
+                ProposalType::Buyback {
+                    payment,
+                    min_tokens,
+                    ..
+                } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if payment == 0 || min_tokens == 0 {
+                        return Err(Error::InvalidBuyback);
+                    }
+                }
+                ProposalType::ConfigureBuyIn {
+                    tokens,
+                    price,
+                    period,
+                    cap,
+                } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if tokens > 0 && (price == 0 || period == 0 || cap == 0) {
+                        return Err(Error::InvalidBuyInConfig);
+                    }
+                }
+                ProposalType::ConfigureMembershipTerm { .. }
+                | ProposalType::SetKickPolicy(_)
+                | ProposalType::SetAllowlisted { .. }
+                | ProposalType::SetJoinFee(_)
+                | ProposalType::SetResignationPolicy(_)
+                | ProposalType::SetMemberOnlyTransfers(_)
+                | ProposalType::SetVestingVotePower(_)
+                | ProposalType::SetUnbondingPeriod(_)
+                | ProposalType::SetAirdropRoot(_)
+                | ProposalType::SetRandomnessOracle(_)
+                | ProposalType::SetExecutor(_)
+                | ProposalType::SetWrappedToken(_)
+                | ProposalType::Upgrade(_)
+                | ProposalType::SetUpgradeTimelock(_)
+                | ProposalType::SetPauseQuorum(_)
+                | ProposalType::Unpause => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                }
+                ProposalType::XcmExecute(ref message) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    Self::decode_xcm::<VersionedXcm<()>>(message)?;
+                }
+                ProposalType::XcmSend {
+                    ref dest,
+                    ref message,
+                } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    Self::decode_xcm::<VersionedLocation>(dest)?;
+                    Self::decode_xcm::<VersionedXcm<()>>(message)?;
+                }
+                ProposalType::SetVoteWeightSource(source) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if source == VoteWeightSource::External {
+                        match &self.external_token {
+                            None => return Err(Error::NoExternalToken),
+                            Some(external) if !external.votes_style => {
+                                return Err(Error::ExternalTokenNotVotes)
+                            }
+                            Some(_) => {}
+                        }
+                    }
+                    if let VoteWeightSource::Blended { reputation_percent } = source {
+                        if reputation_percent > 100 {
+                            return Err(Error::InvalidWeightBlend);
+                        }
+                    }
+                }
+                ProposalType::AddObserver(observer) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if self.members.contains(observer) {
+                        return Err(Error::AlreadyMember);
+                    }
+                    if self.observers.get_or_default().contains(&observer) {
+                        return Err(Error::AlreadyObserver);
+                    }
+                }
+                ProposalType::RemoveObserver(observer) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if !self.observers.get_or_default().contains(&observer) {
+                        return Err(Error::NotObserver);
+                    }
+                }
+                ProposalType::CharterCommittee {
+                    ref name,
+                    ref members,
+                    ref envelope,
+                    quorum,
+                    ..
+                } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if name.is_empty() || quorum == 0 || quorum as usize > members.len() {
+                        return Err(Error::InvalidCommittee);
+                    }
+                    if members
+                        .iter()
+                        .enumerate()
+                        .any(|(i, member)| members[..i].contains(member))
+                    {
+                        return Err(Error::InvalidCommittee);
+                    }
+                    if members.iter().any(|member| !self.members.contains(member)) {
+                        return Err(Error::NotMember);
+                    }
+                    if !self.budgets.contains(envelope) {
+                        return Err(Error::BudgetNotFound);
+                    }
+                }
+                ProposalType::DrawCommittee {
+                    ref name,
+                    size,
+                    ref envelope,
+                    quorum,
+                    ..
+                } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if self.randomness_oracle.is_none() {
+                        return Err(Error::NoRandomnessOracle);
+                    }
+                    if name.is_empty()
+                        || size == 0
+                        || size > self.member_count
+                        || quorum == 0
+                        || quorum > size
+                    {
+                        return Err(Error::InvalidCommittee);
+                    }
+                    if !self.budgets.contains(envelope) {
+                        return Err(Error::BudgetNotFound);
+                    }
+                }
+                ProposalType::RegisterHook {
+                    contract,
+                    ref_time_limit,
+                } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    let hooks = self.hooks.get_or_default();
+                    if ref_time_limit == 0
+                        || ref_time_limit > MAX_HOOK_REF_TIME
+                        || hooks.len() >= MAX_HOOKS as usize
+                        || hooks.iter().any(|hook| hook.contract == contract)
+                    {
+                        return Err(Error::InvalidHook);
+                    }
+                }
+                ProposalType::FreezeMember(member) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if !self.members.contains(member) {
+                        return Err(Error::NotMember);
+                    }
+                    if self.frozen.contains(member) {
+                        return Err(Error::AccountFrozen);
+                    }
+                }
+                ProposalType::UnfreezeMember { member, migrate_to } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if !self.frozen.contains(member) {
+                        return Err(Error::NotFrozen);
+                    }
+                    if migrate_to.is_some_and(|to| self.members.contains(to)) {
+                        return Err(Error::AlreadyMember);
+                    }
+                }
+                ProposalType::Dispute(target) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if !self.members.contains(author) {
+                        return Err(Error::NotMember);
+                    }
+                    if self.dispute_bond == 0 {
+                        return Err(Error::DisputesDisabled);
+                    }
+                    let contested = self.proposals.get(target).ok_or(Error::ProposalNotFound)?;
+                    if contested.status != ProposalStatus::Passed {
+                        return Err(Error::ProposalNotPassed);
+                    }
+                    if matches!(contested.proposal_type, ProposalType::Dispute(_)) {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    // Disputes are raised while members still have time to react
+                    if self.timelocks.get(target).is_none_or(|executable_at| {
+                        executable_at <= self.env().block_number() as u64
+                    }) {
+                        return Err(Error::DisputeWindowClosed);
+                    }
+                    if self.disputes.contains(target) {
+                        return Err(Error::DisputeOpen);
+                    }
+                }
+                ProposalType::ConfigureJury { size, .. } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if size > 0 && self.randomness_oracle.is_none() {
+                        return Err(Error::NoRandomnessOracle);
+                    }
+                    if size > self.member_count {
+                        return Err(Error::InvalidJurySize);
+                    }
+                }
+                ProposalType::Election { seats } => {
+                    if seats == 0 || seats as usize >= options.len() {
+                        return Err(Error::InvalidElection);
+                    }
+                }
+                ProposalType::ConfigureCouncilTerms { term, seats } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if (term > 0 && seats == 0) || seats > MAX_COUNCIL_SIZE {
+                        return Err(Error::InvalidCouncilTerms);
+                    }
+                }
+                ProposalType::ConfigureVoteDecay { bonus_percent, .. } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if bonus_percent > 100 {
+                        return Err(Error::InvalidVoteDecay);
+                    }
+                }
+                ProposalType::ConfigureDisputes { .. } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                }
+                ProposalType::SetRateLimit(ref limit) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if limit.growth_percent > MAX_SURCHARGE_GROWTH_PERCENT {
+                        return Err(Error::InvalidRateLimit);
+                    }
+                }
+                ProposalType::SetDaoMetadata(ref metadata) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if metadata.name.len() > MAX_PROFILE_NAME_LEN
+                        || metadata.description.len() > MAX_DAO_DESCRIPTION_LEN
+                        || metadata.dao_uri.len() > MAX_DAO_URI_LEN
+                    {
+                        return Err(Error::MetadataTooLong);
+                    }
+                }
+                ProposalType::UnregisterHook(contract) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if !self
+                        .hooks
+                        .get_or_default()
+                        .iter()
+                        .any(|hook| hook.contract == contract)
+                    {
+                        return Err(Error::HookNotFound);
+                    }
+                }
+                ProposalType::Lottery { prize } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if self.randomness_oracle.is_none() {
+                        return Err(Error::NoRandomnessOracle);
+                    }
+                    if prize == 0 {
+                        return Err(Error::ZeroAmount);
+                    }
+                }
+                ProposalType::SetMaxMembers(limit) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if limit > MAX_MEMBERS || limit < self.member_count {
+                        return Err(Error::InvalidMemberLimit);
+                    }
+                }
+                ProposalType::DissolveCommittee(committee_id) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    let committee = self
+                        .committees
+                        .get(committee_id)
+                        .ok_or(Error::CommitteeNotFound)?;
+                    if committee.dissolved {
+                        return Err(Error::CommitteeDissolved);
+                    }
+                }
+                ProposalType::TokenMint { recipient, .. }
+                | ProposalType::AwardTokens { recipient, .. } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if !self.members.contains(recipient) {
+                        return Err(Error::NotMember);
+                    }
+                }
+                ProposalType::ConfigureEmission {
+                    amount,
+                    epoch_length,
+                    decay_percent,
+                } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if (amount > 0 && epoch_length == 0) || decay_percent > 100 {
+                        return Err(Error::InvalidEmission);
+                    }
+                }
+                ProposalType::GrantVesting {
+                    recipient,
+                    amount,
+                    cliff,
+                    duration,
+                } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if !self.members.contains(recipient) {
+                        return Err(Error::NotMember);
+                    }
+                    if amount == 0 || duration == 0 || cliff > duration {
+                        return Err(Error::InvalidVesting);
+                    }
+                }
+                ProposalType::SlashReputation { member, .. } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if !self.members.contains(member) {
+                        return Err(Error::NotMember);
+                    }
+                }
+                ProposalType::Slash { member, amount, .. } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if !self.members.contains(member) {
+                        return Err(Error::NotMember);
+                    }
+                    if amount == 0 {
+                        return Err(Error::ZeroAmount);
+                    }
+                }
+                ProposalType::AssignTier { account, .. } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if !self.members.contains(account) {
+                        return Err(Error::NotMember);
+                    }
+                }
+                ProposalType::ConfigureTier { vote_weight, .. } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if vote_weight == 0 {
+                        return Err(Error::InvalidTierConfig);
+                    }
+                }
+                ProposalType::ElectCouncil(ref council) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    self.validate_council(council)?;
+                }
+                ProposalType::AssignRole { account, .. } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if !self.members.contains(account) {
+                        return Err(Error::NotMember);
+                    }
+                }
+                ProposalType::RemoveMember(member) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if !self.members.contains(member) {
+                        return Err(Error::NotMember);
+                    }
+                    if self.member_count == 1 {
+                        return Err(Error::LastMember);
+                    }
+                }
+                ProposalType::ConfigureTips {
+                    cap,
+                    approvals,
+                    budget,
+                    period,
+                } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if cap > 0 && (approvals < 2 || budget < cap || period == 0) {
+                        return Err(Error::InvalidTipConfig);
+                    }
+                }
+                ProposalType::ConfigChange(ref update) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    self.validate_config_update(update)?;
+                }
+                ProposalType::ConfigureProposalType { ref config, .. } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if let Some(config) = config {
+                        self.validate_type_config(config)?;
+                    }
+                }
+                ProposalType::SetMaxRequestPercent(percent) => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if matches!(percent, Some(0) | Some(101..)) {
+                        return Err(Error::InvalidRequestCap);
+                    }
+                }
+                ProposalType::AllocateBudget {
+                    ref envelope,
+                    asset,
+                    amount,
+                } => {
+                    if options.len() != 1 {
+                        return Err(Error::InvalidProposalType);
+                    }
+                    if envelope.is_empty() || amount == 0 {
+                        return Err(Error::InvalidBudget);
+                    }
+                    if let Some(asset) = asset {
+                        if !self.registered_assets.get_or_default().contains(&asset) {
+                            return Err(Error::AssetNotRegistered);
+                        }
+                    }
+                    if let Some(budget) = self.budgets.get(envelope) {
+                        if budget.asset != asset {
+                            return Err(Error::BudgetAssetMismatch);
+                        }
+                    }
+                }
+            }
+
+            let rules = self.type_config(&proposal_type);
+            let deposit = if let ProposalType::Dispute(_) = proposal_type {
+                self.dispute_bond
+            } else {
+                rules
+                    .deposit
+                    .saturating_add(self.rate_limit_surcharge(author))
+            };
+            if Self::to_balance(self.env().transferred_value()) != deposit {
+                return Err(Error::IncorrectDeposit);
+            }
+
+            let proposal_id = self.next_proposal_id;
+            let current_block = self.env().block_number() as u64;
+            let content_hash = self.env().hash_encoded::<Keccak256, _>(&ProposalContent {
+                name: name.clone(),
+                description: description.clone(),
+                proposal_type: proposal_type.clone(),
+                options: options.clone(),
+                amount,
+                terms: terms.clone(),
+            });
+
+            let proposal = Proposal {
+                id: proposal_id,
+                name: name.clone(),
+                description: String::new(),
+                author,
+                proposal_type,
+                options: options.clone(),
+                amount,
+                asset: terms.asset,
+                stream_duration: terms.stream_duration,
+                milestones: terms.milestones,
+                envelope: terms.envelope,
+                tranches: terms.tranches,
+                recipient: terms.recipient,
+                votes: vec![0; options.len()],
+                voter_count: 0,
+                status,
+                created_at: current_block,
+                voting_deadline: current_block + rules.voting_period,
+            };
+
+            if let ProposalType::Dispute(target) = proposal.proposal_type {
+                self.disputes.insert(target, &proposal_id);
+
+                Self::env().emit_event(DisputeRaised {
+                    proposal_id: target,
+                    dispute_id: proposal_id,
+                    challenger: author,
+                    bond: deposit,
+                });
+            }
+
+            self.proposals.insert(proposal_id, &proposal);
+            self.proposal_descriptions.insert(proposal_id, &description);
+            self.content_hashes.insert(proposal_id, &content_hash);
+            self.next_proposal_id += 1;
+            self.lock_deposit(
+                DepositReason::Proposal(proposal_id),
+                self.env().caller(),
+                deposit,
+            );
+            self.count_proposal(author);
+
+            Self::env().emit_event(ProposalCreated {
+                proposal_id,
+                author,
+                name,
+                proposal_type: proposal.proposal_type.clone(),
+                options_count: options.len() as u32,
+                voting_deadline: proposal.voting_deadline,
+                recipient: proposal.recipient,
+            });
+
+            if status == ProposalStatus::Draft {
+                self.draft_proposals += 1;
+            } else {
+                self.open_voting(&proposal, rules.voting_period)?;
+            }
+
+            Ok(proposal_id)
+        }
+
+        /// Start the voting period of a stored proposal, created open or sponsored from a draft
+        fn open_voting(&mut self, proposal: &Proposal, voting_period: u64) -> Result<()> {
+            if let ProposalType::GrantProgress(grant_id) = proposal.proposal_type {
+                let mut grant = self.grants.get(grant_id).ok_or(Error::GrantNotFound)?;
+                // The grant may have moved on while the vote sat in draft
+                if grant.clawed_back || grant.released as usize >= grant.tranches.len() {
+                    return Err(Error::GrantClosed);
+                }
+                if grant.pending_vote.is_some() {
+                    return Err(Error::GrantVotePending);
+                }
+                grant.pending_vote = Some(proposal.id);
+                self.grants.insert(grant_id, &grant);
+            }
+
+            self.snapshot_voting_power(proposal.id);
+            self.insert_active_proposal(proposal.id, proposal.voting_deadline, voting_period);
+
+            // Kinds decided by a jury take votes once it is drawn
+            if self
+                .jury_sizes
+                .contains(Self::kind_of(&proposal.proposal_type))
+            {
+                self.request_draw(proposal.id)?;
+            }
+
+            Ok(())
+        }
+
+        /// Charge an executed money request against its budget envelope
+        fn spend_budget(
+            &mut self,
+            envelope: &str,
+            asset: Option<H160>,
+            amount: Balance,
+        ) -> Result<()> {
+            let mut budget = self.budgets.get(envelope).ok_or(Error::BudgetNotFound)?;
+
+            if budget.asset != asset {
+                return Err(Error::BudgetAssetMismatch);
+            }
+            if budget.allocated - budget.spent < amount {
+                return Err(Error::BudgetExceeded);
+            }
+
+            budget.spent += amount;
+            self.budgets.insert(envelope, &budget);
+
+            Ok(())
+        }
+
+        /// Hold funds sent along with a call until the related flow is settled
+        fn lock_deposit(&mut self, reason: DepositReason, owner: H160, amount: Balance) {
+            if amount == 0 {
+                return;
+            }
+
+            let reserved = self.reserved_funds.get(None::<H160>).unwrap_or(0);
+            self.reserved_funds
+                .insert(None::<H160>, &(reserved + amount));
+            self.deposits.insert(reason, &Deposit { owner, amount });
+
+            Self::env().emit_event(DepositLocked {
+                owner,
+                reason,
+                amount,
+            });
+        }
+
+        /// Make a locked deposit claimable by its owner through `claim_refund`
+        fn refund_deposit(&mut self, reason: DepositReason) {
+            let Some(deposit) = self.deposits.take(reason) else {
+                return;
+            };
+
+            let refund = self.refunds.get(deposit.owner).unwrap_or(0);
+            self.refunds
+                .insert(deposit.owner, &(refund + deposit.amount));
+
+            Self::env().emit_event(DepositRefunded {
+                owner: deposit.owner,
+                reason,
+                amount: deposit.amount,
+            });
+        }
+
+        /// Move a locked deposit into the treasury
+        fn forfeit_deposit(&mut self, reason: DepositReason) {
+            let Some(deposit) = self.deposits.take(reason) else {
+                return;
+            };
+
+            self.unreserve_funds(None, deposit.amount);
+
+            Self::env().emit_event(DepositForfeited {
+                owner: deposit.owner,
+                reason,
+                amount: deposit.amount,
+            });
+        }
+
+        /// Bookkeeping once a proposal leaves the `Active` status
+        fn on_voting_closed(&mut self, proposal: &Proposal) {
+            self.remove_active_proposal(proposal.id);
+            self.advance_deadline_queues();
+
+            match proposal.status {
+                ProposalStatus::Passed => {
+                    self.passed_proposals += 1;
+                    self.track_pending_request(proposal, true);
+                }
+                ProposalStatus::Rejected => self.rejected_proposals += 1,
+                ProposalStatus::Expired => self.expired_proposals += 1,
+                _ => {}
+            }
+
+            Self::env().emit_event(ProposalFinalized {
+                proposal_id: proposal.id,
+                status: proposal.status,
+                votes: proposal.votes.clone(),
+                turnout: proposal.voter_count,
+            });
+            self.settle_proposal_deposit(proposal);
+            self.accrue_vote_rebates(proposal);
+
+            if proposal.status == ProposalStatus::Passed {
+                self.add_reputation(proposal.author, REPUTATION_PER_PASSED_PROPOSAL);
+                self.notify_hooks(HookEvent::ProposalPassed(proposal.id));
+
+                // Sensitive actions give members time to react before they run
+                let mut timelock = self.type_config(&proposal.proposal_type).timelock;
+                let sensitive = Self::is_sensitive(&proposal.proposal_type);
+                if sensitive {
+                    timelock = timelock.max(self.upgrade_timelock);
+                }
+                if sensitive || timelock > 0 {
+                    let executable_at = (self.env().block_number() as u64).saturating_add(timelock);
+                    self.timelocks.insert(proposal.id, &executable_at);
+
+                    Self::env().emit_event(ActionQueued {
+                        proposal_id: proposal.id,
+                        executable_at,
+                    });
+                }
+            }
+            self.record_participation(proposal);
+
+            if let ProposalType::Dispute(target) = proposal.proposal_type {
+                self.settle_dispute(proposal, target);
+            }
+
+            if let ProposalType::GrantProgress(grant_id) = proposal.proposal_type {
+                if proposal.status == ProposalStatus::Passed {
+                    return;
+                }
+                let Some(mut grant) = self.grants.get(grant_id) else {
+                    return;
+                };
+                // A vote that expired without quorum leaves the grant as it is for a re-vote
+                if proposal.status != ProposalStatus::Rejected {
+                    grant.pending_vote = None;
+                    self.grants.insert(grant_id, &grant);
+                    return;
+                }
+                // A rejected progress vote claws back all remaining tranches
+                let amount = grant.tranches[grant.released as usize..].iter().sum();
+                grant.clawed_back = true;
+                grant.pending_vote = None;
+                self.grants.insert(grant_id, &grant);
+                self.unreserve_funds(grant.asset, amount);
+
+                Self::env().emit_event(GrantClawedBack { grant_id, amount });
+            }
+        }
+
+        /// Set the rebate each voter of a closed proposal earns, capped per proposal. Voters
+        /// are credited on settling their participation, while the treasury covers it.
+        fn accrue_vote_rebates(&mut self, proposal: &Proposal) {
+            if self.vote_rebate == 0 || proposal.voter_count == 0 {
+                return;
+            }
+            let voters = proposal.voter_count as Balance;
+            let per_voter = self.vote_rebate.min(self.max_rebate_per_proposal / voters);
+            if per_voter == 0 {
+                return;
+            }
+            self.proposal_rebates.insert(proposal.id, &per_voter);
+
+            Self::env().emit_event(RebatesAccrued {
+                proposal_id: proposal.id,
+                per_voter,
+                voters: proposal.voter_count,
+            });
+        }
+
+        /// Proposals that reached the vote minimum get their deposit back, ignored ones forfeit it.
+        /// A dispute bond is only returned if the dispute succeeds or the arbiter never decided.
+        fn settle_proposal_deposit(&mut self, proposal: &Proposal) {
+            let reason = DepositReason::Proposal(proposal.id);
+            let total_votes = Self::total_votes(proposal);
+
+            if let ProposalType::Dispute(_) = proposal.proposal_type {
+                let undecided =
+                    self.arbiter.is_some() && proposal.status == ProposalStatus::Expired;
+                if proposal.status == ProposalStatus::Passed || undecided {
+                    self.refund_deposit(reason);
+                } else {
+                    self.forfeit_deposit(reason);
+                }
+                return;
+            }
+
+            if total_votes >= self.quorum(proposal) {
+                self.refund_deposit(reason);
+            } else {
+                self.forfeit_deposit(reason);
+            }
+        }
+
+        /// Release a contested proposal from its dispute, rejecting it if the dispute passed
+        fn settle_dispute(&mut self, dispute: &Proposal, target: u32) {
+            self.disputes.remove(target);
+
+            let overturned = dispute.status == ProposalStatus::Passed;
+            if overturned {
+                if let Some(mut contested) = self.proposals.get(target) {
+                    // The council may have vetoed it in the meantime
+                    if Self::set_status(&mut contested, ProposalStatus::Rejected).is_ok() {
+                        self.proposals.insert(target, &contested);
+                        self.track_pending_request(&contested, false);
+                        self.timelocks.remove(target);
+                        self.passed_proposals -= 1;
+                        self.rejected_proposals += 1;
+                    }
+                }
+            }
+
+            Self::env().emit_event(DisputeResolved {
+                proposal_id: target,
+                dispute_id: dispute.id,
+                overturned,
+            });
+        }
+
+        /// Lock the money request amount in a new stream paying out over `duration` blocks
+        fn open_stream(
+            &mut self,
+            proposal: &Proposal,
+            total: Balance,
+            duration: u64,
+        ) -> Result<()> {
+            self.reserve_funds(proposal.asset, total)?;
+
+            let stream_id = self.next_stream_id;
+            let start_block = self.env().block_number() as u64;
+            let stream = Stream {
+                id: stream_id,
+                proposal_id: proposal.id,
+                recipient: Self::payee_of(proposal),
+                asset: proposal.asset,
+                total,
+                withdrawn: 0,
+                start_block,
+                end_block: start_block + duration,
+                cancelled_at: None,
+            };
+
+            self.streams.insert(stream_id, &stream);
+            self.next_stream_id += 1;
+
+            Self::env().emit_event(StreamCreated {
+                stream_id,
+                recipient: stream.recipient,
+                proposal_id: proposal.id,
+                total,
+                end_block: stream.end_block,
+            });
+
+            Ok(())
+        }
+
+        /// Stop accrual of a stream; the recipient keeps what accrued so far
+        fn cancel_stream(&mut self, stream_id: u32) -> Result<()> {
+            let mut stream = self.streams.get(stream_id).ok_or(Error::StreamNotFound)?;
+
+            if stream.cancelled_at.is_some() {
+                return Err(Error::StreamAlreadyCancelled);
+            }
+
+            stream.cancelled_at = Some(self.env().block_number() as u64);
+            let refunded = stream.total - self.accrued(&stream);
+            self.streams.insert(stream_id, &stream);
+            self.unreserve_funds(stream.asset, refunded);
+
+            Self::env().emit_event(StreamCancelled {
+                stream_id,
+                refunded,
+            });
+
+            Ok(())
+        }
+
+        /// Lock the money request amount in escrow, released per approved milestone
+        fn open_escrow(&mut self, proposal: &Proposal, total: Balance) -> Result<()> {
+            self.reserve_funds(proposal.asset, total)?;
+
+            let escrow_id = self.next_escrow_id;
+            let milestones = proposal
+                .milestones
+                .iter()
+                .map(|amount| Milestone {
+                    amount: *amount,
+                    status: MilestoneStatus::Pending,
+                    approvals: Vec::new(),
+                    rejections: Vec::new(),
+                })
+                .collect();
+            let escrow = Escrow {
+                id: escrow_id,
+                proposal_id: proposal.id,
+                recipient: Self::payee_of(proposal),
+                asset: proposal.asset,
+                milestones,
+                cancelled: false,
+            };
+
+            self.escrows.insert(escrow_id, &escrow);
+            self.next_escrow_id += 1;
+
+            Self::env().emit_event(EscrowCreated {
+                escrow_id,
+                recipient: escrow.recipient,
+                proposal_id: proposal.id,
+                total,
+            });
+
+            Ok(())
+        }
+
+        /// Account a money request pays out to
+        fn payee_of(proposal: &Proposal) -> H160 {
+            proposal.recipient.unwrap_or(proposal.author)
+        }
+
+        /// Lock the grant amount and pay out the first tranche
+        fn open_grant(&mut self, proposal: &Proposal, total: Balance) -> Result<()> {
+            self.reserve_funds(proposal.asset, total)?;
+
+            let grant_id = self.next_grant_id;
+            let grant = Grant {
+                id: grant_id,
+                proposal_id: proposal.id,
+                recipient: Self::payee_of(proposal),
+                asset: proposal.asset,
+                tranches: proposal.tranches.clone(),
+                released: 0,
+                pending_vote: None,
+                clawed_back: false,
+            };
+            self.grants.insert(grant_id, &grant);
+            self.next_grant_id += 1;
+
+            Self::env().emit_event(GrantCreated {
+                grant_id,
+                recipient: grant.recipient,
+                proposal_id: proposal.id,
+                total,
+            });
+
+            self.release_grant_tranche(grant_id)
+        }
+
+        /// Pay out the next tranche of a grant
+        fn release_grant_tranche(&mut self, grant_id: u32) -> Result<()> {
+            let mut grant = self.grants.get(grant_id).ok_or(Error::GrantNotFound)?;
+
+            let tranche = grant.released;
+            let amount = *grant
+                .tranches
+                .get(tranche as usize)
+                .ok_or(Error::GrantClosed)?;
+            if grant.clawed_back {
+                return Err(Error::GrantClosed);
+            }
+
+            grant.released += 1;
+            grant.pending_vote = None;
+            self.grants.insert(grant_id, &grant);
+
+            self.unreserve_funds(grant.asset, amount);
+            self.credit_payout(grant.asset, grant.recipient, amount)?;
+
+            Self::env().emit_event(GrantTrancheReleased {
+                grant_id,
+                tranche,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Return all unreleased milestone funds of an escrow to the treasury
+        fn cancel_escrow(&mut self, escrow_id: u32) -> Result<()> {
+            let mut escrow = self.escrows.get(escrow_id).ok_or(Error::EscrowNotFound)?;
+
+            if escrow.cancelled {
+                return Err(Error::EscrowAlreadyCancelled);
+            }
+
+            let refunded = escrow
+                .milestones
+                .iter()
+                .filter(|milestone| milestone.status != MilestoneStatus::Released)
+                .map(|milestone| milestone.amount)
+                .sum();
+            escrow.cancelled = true;
+            self.escrows.insert(escrow_id, &escrow);
+            self.unreserve_funds(escrow.asset, refunded);
+
+            Self::env().emit_event(EscrowCancelled {
+                escrow_id,
+                refunded,
+            });
+
+            Ok(())
+        }
+
+        /// Total amount of a stream accrued up to the current block
+        fn accrued(&self, stream: &Stream) -> Balance {
+            let now = self.env().block_number() as u64;
+            let until = stream
+                .cancelled_at
+                .unwrap_or(stream.end_block)
+                .min(stream.end_block)
+                .min(now);
+            let elapsed = until.saturating_sub(stream.start_block);
+            let duration = stream.end_block - stream.start_block;
+
+            stream.total.saturating_mul(elapsed as Balance) / duration as Balance
+        }
+
+        /// Take up to `amount` tokens from a holder, locked ones included, burning
+        /// them or moving them to the DAO's token pool. Returns the tokens taken.
+        fn slash_tokens(
+            &mut self,
+            holder: H160,
+            amount: Balance,
+            to_treasury: bool,
+        ) -> Result<Balance> {
+            self.settle_dividends(holder);
+            let balance = self.member_tokens.get(holder).unwrap_or(0);
+            let slashed = amount.min(balance);
+            let remaining = balance - slashed;
+            self.member_tokens.insert(holder, &remaining);
+            self.checkpoint_balance(holder);
+
+            // Locks can't exceed what is left, stake is slashed last
+            let staked = self.staked.get(holder).unwrap_or(0).min(remaining);
+            self.set_stake(holder, staked);
+            if let Some(mut unbonding) = self.unbonding.get(holder) {
+                unbonding.amount = unbonding.amount.min(remaining - staked);
+                self.unbonding.insert(holder, &unbonding);
+            }
+
+            if to_treasury {
+                self.treasury_tokens = self
+                    .treasury_tokens
+                    .checked_add(slashed)
+                    .ok_or(Error::Overflow)?;
+            } else {
+                self.total_supply -= slashed;
+            }
+
+            Self::env().emit_event(Transfer {
+                from: Some(holder),
+                to: None,
+                value: slashed,
+            });
+
+            Ok(slashed)
+        }
+
+        /// Burn tokens the holder approved the DAO to take
+        fn burn_from(&mut self, holder: H160, amount: Balance) -> Result<()> {
+            let spender = self.env().address();
+            let allowance = self.allowances.get((holder, spender)).unwrap_or(0);
+            if allowance < amount || self.unlocked_tokens(holder) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.settle_dividends(holder);
+            let balance = self.member_tokens.get(holder).unwrap_or(0);
+            self.member_tokens.insert(holder, &(balance - amount));
+            self.checkpoint_balance(holder);
+            self.allowances
+                .insert((holder, spender), &(allowance - amount));
+            self.total_supply -= amount;
+
+            Self::env().emit_event(Transfer {
+                from: Some(holder),
+                to: None,
+                value: amount,
+            });
+
+            Ok(())
+        }
+
+        fn mint_tokens(&mut self, recipient: H160, amount: Balance) -> Result<()> {
+            // Only existing members can receive tokens
+            if !self.members.contains(recipient) {
+                return Err(Error::NotMember);
+            }
+            self.issue_tokens(recipient, amount)
+        }
+
+        /// Mint tokens to any holder, used where the tokens are paid for in full
+        fn issue_tokens(&mut self, recipient: H160, amount: Balance) -> Result<()> {
+            self.settle_dividends(recipient);
+            let current_balance = self.member_tokens.get(recipient).unwrap_or(0);
+            let new_balance = current_balance.checked_add(amount).ok_or(Error::Overflow)?;
+            let total_supply = self
+                .total_supply
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            self.member_tokens.insert(recipient, &new_balance);
+            self.checkpoint_balance(recipient);
+            self.total_supply = total_supply;
+
+            Self::env().emit_event(TokensDistributed { recipient, amount });
+            Self::env().emit_event(Transfer {
+                from: None,
+                to: Some(recipient),
+                value: amount,
+            });
+
+            Ok(())
+        }
+
+        /// DAO error matching a failed `move_tokens`
+        fn token_error(error: PSP22Error) -> Error {
+            match error {
+                PSP22Error::InsufficientBalance => Error::InsufficientBalance,
+                PSP22Error::ZeroRecipientAddress => Error::InvalidRecipient,
+                PSP22Error::Custom(reason) => match reason.as_str() {
+                    "SoulboundToken" => Error::SoulboundToken,
+                    "AccountFrozen" => Error::AccountFrozen,
+                    "RecipientNotMember" => Error::RecipientNotMember,
+                    "Overflow" => Error::Overflow,
+                    _ => Error::TransferFailed,
+                },
+                _ => Error::TransferFailed,
+            }
+        }
+
+        /// Move governance tokens between holders
+        fn move_tokens(
+            &mut self,
+            from: H160,
+            to: H160,
+            value: Balance,
+        ) -> core::result::Result<(), PSP22Error> {
+            if self.soulbound {
+                return Err(PSP22Error::Custom(String::from("SoulboundToken")));
+            }
+            if self.frozen.contains(from) {
+                return Err(PSP22Error::Custom(String::from("AccountFrozen")));
+            }
+            if to == H160::zero() {
+                return Err(PSP22Error::ZeroRecipientAddress);
+            }
+            if self.member_only_transfers && !self.members.contains(to) {
+                return Err(PSP22Error::Custom(String::from("RecipientNotMember")));
+            }
+
+            let from_balance = self.member_tokens.get(from).unwrap_or(0);
+            if self.unlocked_tokens(from) < value {
+                return Err(PSP22Error::InsufficientBalance);
+            }
+            if from == to || value == 0 {
+                return Ok(());
+            }
+
+            let to_balance = self
+                .member_tokens
+                .get(to)
+                .unwrap_or(0)
+                .checked_add(value)
+                .ok_or(PSP22Error::Custom(String::from("Overflow")))?;
+            self.settle_dividends(from);
+            self.settle_dividends(to);
+            self.member_tokens.insert(from, &(from_balance - value));
+            self.member_tokens.insert(to, &to_balance);
+            // Unbacked shares go first, backing the sender can no longer hold moves along
+            let backing = self.wrapped_deposits.get(from).unwrap_or(0);
+            let remaining = from_balance - value;
+            if backing > remaining {
+                self.wrapped_deposits.insert(from, &remaining);
+                let received = self.wrapped_deposits.get(to).unwrap_or(0);
+                self.wrapped_deposits
+                    .insert(to, &received.saturating_add(backing - remaining));
+            }
+            self.checkpoint_balance(from);
+            self.checkpoint_balance(to);
+
+            Self::env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Treasury holdings of native balance or a PSP22 asset
+        fn held_funds(&self, asset: Option<H160>) -> Balance {
+            match asset {
+                None => self.get_treasury_balance(),
+                Some(asset) => self.asset_balances.get(asset).unwrap_or(0),
+            }
+        }
+
+        /// Treasury holdings of native balance or a PSP22 asset not committed elsewhere
+        fn free_funds(&self, asset: Option<H160>) -> Balance {
+            self.held_funds(asset)
+                .saturating_sub(self.reserved_funds.get(asset).unwrap_or(0))
+        }
+
+        /// Reject requests above the configured share of the current treasury holdings
+        fn ensure_within_request_cap(&self, asset: Option<H160>, amount: Balance) -> Result<()> {
+            self.ensure_within_cap_of(self.held_funds(asset), amount)
+        }
+
+        /// Reject spending `amount` out of `held` above the configured share
+        fn ensure_within_cap_of(&self, held: Balance, amount: Balance) -> Result<()> {
+            let Some(percent) = self.max_request_percent else {
+                return Ok(());
+            };
+
+            if amount.saturating_mul(100) > held.saturating_mul(percent as Balance) {
+                return Err(Error::RequestExceedsTreasuryCap);
+            }
+
+            Ok(())
+        }
+
+        fn reserve_funds(&mut self, asset: Option<H160>, amount: Balance) -> Result<()> {
+            if self.free_funds(asset) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+            let reserved = self.reserved_funds.get(asset).unwrap_or(0);
+            let reserved = reserved.checked_add(amount).ok_or(Error::Overflow)?;
+            self.reserved_funds.insert(asset, &reserved);
+            Ok(())
+        }
+
+        fn unreserve_funds(&mut self, asset: Option<H160>, amount: Balance) {
+            let reserved = self.reserved_funds.get(asset).unwrap_or(0);
+            self.reserved_funds
+                .insert(asset, &reserved.saturating_sub(amount));
+        }
+
+        /// Amount requested by passed money requests that haven't been executed yet
+        fn pending_requests(&self, asset: Option<H160>) -> Balance {
+            self.pending_requests.get(asset).unwrap_or(0)
+        }
+
+        /// Count a request into the pending total once it passed, and out again once it
+        /// leaves the `Passed` status
+        fn track_pending_request(&mut self, proposal: &Proposal, pending: bool) {
+            let amount = Self::requested_amount(proposal);
+            if amount == 0 {
+                return;
+            }
+            let total = self.pending_requests(proposal.asset);
+            let total = if pending {
+                total.saturating_add(amount)
+            } else {
+                total.saturating_sub(amount)
+            };
+            self.pending_requests.insert(proposal.asset, &total);
+        }
+
+        /// Funds a passed proposal pays out once executed
+        fn requested_amount(proposal: &Proposal) -> Balance {
+            match &proposal.proposal_type {
+                ProposalType::MoneyRequest => proposal.amount.unwrap_or(0),
+                ProposalType::BudgetVote { amounts } => {
+                    amounts[Self::leading_option(proposal)].unwrap_or(0)
+                }
+                _ => 0,
+            }
+        }
+
+        /// Option with the most votes, the earliest on the ballot among equals
+        fn leading_option(proposal: &Proposal) -> usize {
+            let max_votes = proposal.votes.iter().max().unwrap_or(&0);
+            proposal
+                .votes
+                .iter()
+                .position(|votes| votes == max_votes)
+                .unwrap_or(0)
+        }
+
+        /// Set funds aside for `to`, who withdraws them with `claim_payout`
+        fn credit_payout(&mut self, asset: Option<H160>, to: H160, amount: Balance) -> Result<()> {
+            let claimable = self.claimable.get((to, asset)).unwrap_or(0);
+            let claimable = claimable.checked_add(amount).ok_or(Error::Overflow)?;
+            self.reserve_funds(asset, amount)?;
+            self.claimable.insert((to, asset), &claimable);
+
+            Self::env().emit_event(PayoutCredited {
+                recipient: to,
+                asset,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Tell every registered hook about a governance event. Hooks that revert or run out
+        /// of gas never block governance, they are only reported.
+        fn notify_hooks(&mut self, event: HookEvent) {
+            self.queue_notifications(&event);
+
+            for hook in self.hooks.get_or_default() {
+                let mut listener: ink::contract_ref_from_path!(GovernanceHook) =
+                    hook.contract.into();
+                let delivered = self.guarded(|_| {
+                    listener
+                        .call_mut()
+                        .on_governance_event(event.clone())
+                        .ref_time_limit(hook.ref_time_limit)
+                        .try_invoke()
+                });
+                if !matches!(delivered, Ok(Ok(Ok(())))) {
+                    Self::env().emit_event(HookFailed {
+                        contract: hook.contract,
+                        event: event.clone(),
+                    });
+                }
+            }
+        }
+
+        /// Append an event to the queue of every account subscribed to it
+        fn queue_notifications(&mut self, event: &HookEvent) {
+            let topics = match *event {
+                HookEvent::ProposalPassed(proposal_id)
+                | HookEvent::ProposalExecuted(proposal_id) => {
+                    vec![Subscription::Proposal(proposal_id), Subscription::Proposals]
+                }
+                HookEvent::MemberJoined(_)
+                | HookEvent::MemberLeft(_)
+                | HookEvent::MembershipTransferred { .. } => vec![Subscription::Membership],
+            };
+            let mut recipients: Vec<H160> = Vec::new();
+            for topic in topics {
+                for subscriber in self.subscribers.get(topic).unwrap_or_default() {
+                    if !recipients.contains(&subscriber) {
+                        recipients.push(subscriber);
+                    }
+                }
+            }
+
+            let notification = Notification {
+                event: event.clone(),
+                block: self.env().block_number() as u64,
+            };
+            for subscriber in recipients {
+                let (mut head, tail) = self.notification_bounds.get(subscriber).unwrap_or((0, 0));
+                // A full queue drops its oldest entry
+                if tail - head >= MAX_UNREAD_NOTIFICATIONS {
+                    self.notifications.remove((subscriber, head));
+                    head += 1;
+                }
+                self.notifications.insert((subscriber, tail), &notification);
+                self.notification_bounds
+                    .insert(subscriber, &(head, tail + 1));
+            }
+        }
+
+        /// A passed proposal that may be executed right now
+        fn executable_proposal(&self, proposal_id: u32) -> Result<Proposal> {
+            let proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+
+            if proposal.status == ProposalStatus::Executed {
+                return Err(Error::AlreadyExecuted);
+            }
+            if proposal.status != ProposalStatus::Passed {
+                return Err(Error::ProposalNotPassed);
+            }
+            self.ensure_not_paused(&proposal.proposal_type)?;
+            if self.disputes.contains(proposal_id) {
+                return Err(Error::DisputeOpen);
+            }
+            if let Some(executable_at) = self.timelocks.get(proposal_id) {
+                if (self.env().block_number() as u64) < executable_at {
+                    return Err(Error::TimelockActive);
+                }
+            }
+            Ok(proposal)
+        }
+
+        /// Members execute proposals unless an executor is configured. Members can still
+        /// replace an executor that stopped acting.
+        fn ensure_may_execute(&self, caller: H160, proposal_type: &ProposalType) -> Result<()> {
+            match self.executor {
+                Some(executor) if !matches!(proposal_type, ProposalType::SetExecutor(_)) => {
+                    if caller != executor {
+                        return Err(Error::NotExecutor);
+                    }
+                }
+                _ => {
+                    if !self.members.contains(caller) {
+                        return Err(Error::NotMember);
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        /// Record a new committee, returning its id
+        fn charter_committee(
+            &mut self,
+            name: String,
+            members: Vec<H160>,
+            envelope: String,
+            quorum: u32,
+            max_request: Balance,
+        ) -> u32 {
+            let committee_id = self.next_committee_id;
+            for member in &members {
+                let mut seats = self.committee_seats.get(member).unwrap_or_default();
+                seats.push(committee_id);
+                self.committee_seats.insert(member, &seats);
+            }
+            let committee = Committee {
+                id: committee_id,
+                name: name.clone(),
+                members,
+                envelope: envelope.clone(),
+                quorum,
+                max_request,
+                dissolved: false,
+            };
+            self.committees.insert(committee_id, &committee);
+            self.next_committee_id += 1;
+
+            Self::env().emit_event(CommitteeChartered {
+                committee_id,
+                name,
+                envelope,
+            });
+
+            committee_id
+        }
+
+        /// Forget the seats and open spends of a dissolved committee
+        fn close_committee(&mut self, committee: &Committee) {
+            for member in &committee.members {
+                let mut seats = self.committee_seats.get(member).unwrap_or_default();
+                seats.retain(|id| *id != committee.id);
+                if seats.is_empty() {
+                    self.committee_seats.remove(member);
+                } else {
+                    self.committee_seats.insert(member, &seats);
+                }
+            }
+            self.open_committee_spends.remove(committee.id);
+        }
+
+        /// Take a departing member off every committee along with their votes on open
+        /// spends. A committee left smaller than its quorum decides with everyone left,
+        /// one left empty is dissolved.
+        fn vacate_committee_seats(&mut self, member: H160) {
+            for committee_id in self.committee_seats.take(member).unwrap_or_default() {
+                let Some(mut committee) = self.committees.get(committee_id) else {
+                    continue;
+                };
+                committee.members.retain(|m| *m != member);
+                committee.quorum = committee.quorum.min(committee.members.len() as u32);
+                for spend_id in self
+                    .open_committee_spends
+                    .get(committee_id)
+                    .unwrap_or_default()
+                {
+                    if let Some(mut spend) = self.committee_proposals.get(spend_id) {
+                        spend.approvals.retain(|m| *m != member);
+                        spend.rejections.retain(|m| *m != member);
+                        self.committee_proposals.insert(spend_id, &spend);
+                    }
+                }
+                if committee.members.is_empty() && !committee.dissolved {
+                    committee.dissolved = true;
+                    self.open_committee_spends.remove(committee_id);
+
+                    Self::env().emit_event(CommitteeDissolved { committee_id });
+                }
+                self.committees.insert(committee_id, &committee);
+            }
+        }
+
+        /// Hand a member's committee seats and votes on open spends to their new address
+        fn move_committee_seats(&mut self, from: H160, to: H160) {
+            let Some(seats) = self.committee_seats.take(from) else {
+                return;
+            };
+            for committee_id in &seats {
+                let Some(mut committee) = self.committees.get(committee_id) else {
+                    continue;
+                };
+                for seat in committee.members.iter_mut().filter(|m| **m == from) {
+                    *seat = to;
+                }
+                self.committees.insert(committee_id, &committee);
+                for spend_id in self
+                    .open_committee_spends
+                    .get(committee_id)
+                    .unwrap_or_default()
+                {
+                    if let Some(mut spend) = self.committee_proposals.get(spend_id) {
+                        for voter in spend
+                            .approvals
+                            .iter_mut()
+                            .chain(spend.rejections.iter_mut())
+                            .filter(|m| **m == from)
+                        {
+                            *voter = to;
+                        }
+                        self.committee_proposals.insert(spend_id, &spend);
+                    }
+                }
+            }
+            self.committee_seats.insert(to, &seats);
+        }
+
+        /// Ask the oracle for the randomness a passed draw proposal consumes later
+        fn request_draw(&mut self, proposal_id: u32) -> Result<()> {
+            let oracle = self.randomness_oracle.ok_or(Error::NoRandomnessOracle)?;
+            let mut oracle_ref: ink::contract_ref_from_path!(RandomnessOracle) = oracle.into();
+            let seed = sortition::seed(proposal_id, self.env().block_number() as u64);
+            let request_id = self.guarded(|_| oracle_ref.request_randomness(seed))?;
+            let draw = PendingDraw {
+                oracle,
+                request_id,
+                candidates: self.member_list(),
+            };
+            self.pending_draws.insert(proposal_id, &draw);
+
+            Self::env().emit_event(RandomnessRequested {
+                proposal_id,
+                oracle,
+                request_id,
+            });
+
+            Ok(())
+        }
+
+        /// Pay `amount` of native balance or a PSP22 asset out of the treasury
+        fn release_funds(&mut self, asset: Option<H160>, to: H160, amount: Balance) -> Result<()> {
+            if self.free_funds(asset) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            match asset {
+                None => self
+                    .guarded(|dao| dao.env().transfer(to, U256::from(amount)))?
+                    .map_err(|_| Error::TransferFailed),
+                Some(asset) => {
+                    let available = self.asset_balances.get(asset).unwrap_or(0);
+                    self.asset_balances.insert(asset, &(available - amount));
+
+                    let mut token: ink::contract_ref_from_path!(PSP22) = asset.into();
+                    self.guarded(|_| token.transfer(to, amount, Vec::new()))?
+                        .map_err(|_| Error::TransferFailed)
+                }
+            }
+        }
+
+        /// Move a donor's new total into place on the leaderboard. Totals only grow, so a
+        /// donor left off it is never ahead of the last entry.
+        fn rank_donor(&mut self, donor: H160, total: Balance) {
+            let mut donors = self.top_donors.get_or_default();
+            donors.retain(|(d, _)| *d != donor);
+            // Earlier donors stay ahead on equal totals
+            let position = donors.partition_point(|(_, t)| *t >= total);
+            if position < MAX_TOP_DONORS {
+                donors.insert(position, (donor, total));
+                donors.truncate(MAX_TOP_DONORS);
+                self.top_donors.set(&donors);
+            }
+        }
+
+        fn record_inflow(&mut self, amount: Balance) {
+            self.treasury_inflow = self.treasury_inflow.saturating_add(amount);
+        }
+
+        fn record_outflow(&mut self, amount: Balance) {
+            self.treasury_outflow = self.treasury_outflow.saturating_add(amount);
+        }
+
+        /// XCM program or location of a proposal, bounded like the runtime decodes them
+        fn decode_xcm<T: DecodeLimit>(encoded: &[u8]) -> Result<T> {
+            T::decode_all_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut &encoded[..])
+                .map_err(|_| Error::InvalidXcm)
+        }
+
+        /// Account for native funds an XCM program moved out. Funds committed to streams,
+        /// escrows and other obligations must stay in the treasury, and the outflow is held
+        /// to the same cap as a money request.
+        fn settle_xcm_outflow(&mut self, proposal_id: u32, held_before: Balance) -> Result<()> {
+            let held = self.held_funds(None);
+            if held < self.reserved_funds.get(None::<H160>).unwrap_or(0) {
+                return Err(Error::InsufficientBalance);
+            }
+            let outflow = held_before.saturating_sub(held);
+            self.ensure_within_cap_of(held_before, outflow)?;
+            self.record_outflow(outflow);
+
+            Self::env().emit_event(XcmDispatched {
+                proposal_id,
+                outflow,
+            });
+
+            Ok(())
+        }
+
+        /// Votes cast on a proposal, saturating instead of overflowing
+        fn total_votes(proposal: &Proposal) -> u32 {
+            proposal
+                .votes
+                .iter()
+                .fold(0u32, |total, votes| total.saturating_add(*votes))
+        }
+
+        /// Weighted votes a proposal needs to be decided, in the quorum mode set at its creation
+        fn quorum(&self, proposal: &Proposal) -> u32 {
+            let jury = self.juries.get(proposal.id);
+            match self.quorum_modes.get(proposal.id).unwrap_or_default() {
+                QuorumMode::Votes => {
+                    let min_votes = self.type_config(&proposal.proposal_type).min_votes_required;
+                    // A jury smaller than the quorum decides once all jurors voted
+                    match jury {
+                        Some(jury) => min_votes.min(jury.len() as u32),
+                        None => min_votes,
+                    }
+                }
+                QuorumMode::PowerPercent(percent) => {
+                    // Only the jurors' power counts on a jury proposal
+                    let power = match jury {
+                        Some(jury) => jury
+                            .iter()
+                            .map(|juror| self.vote_weight_of(*juror, proposal.created_at))
+                            .fold(0u32, u32::saturating_add),
+                        None => self.voting_power_snapshots.get(proposal.id).unwrap_or(0),
+                    };
+                    let quorum = (power as u64 * percent as u64).div_ceil(100);
+                    (quorum as u32).max(1)
+                }
+            }
+        }
+
+        /// Sum of the vote weights of all members, read from totals kept up to date as
+        /// members, tiers, reputation and stake change. Unclaimed vested tokens are left
+        /// out and blended weights are blended on the totals. With an external token it
+        /// is the token's whole supply, held by members or not.
+        fn total_voting_power(&self) -> u32 {
+            let clamp = |total: u128| u32::try_from(total).unwrap_or(u32::MAX);
+            match self.vote_weight_source {
+                VoteWeightSource::Tier => [Tier::Core, Tier::Contributor, Tier::Supporter]
+                    .into_iter()
+                    .map(|tier| {
+                        let members = self.tier_counts.get(tier).unwrap_or(0) as u128;
+                        let weight = self.tier_configs.get(tier).unwrap_or_default().vote_weight;
+                        clamp(members * weight as u128)
+                    })
+                    .fold(0u32, u32::saturating_add),
+                // Members without reputation weigh 1
+                VoteWeightSource::Reputation => clamp(
+                    self.total_reputation as u128
+                        + self.member_count.saturating_sub(self.reputation_holders) as u128,
+                ),
+                VoteWeightSource::Tokens => clamp(self.total_staked),
+                VoteWeightSource::External => {
+                    let Some(external) = &self.external_token else {
+                        return 0;
+                    };
+                    let token: ink::contract_ref_from_path!(PSP22) = external.token.into();
+                    clamp(token.total_supply() / external.tokens_per_vote)
+                }
+                VoteWeightSource::Blended { reputation_percent } => {
+                    let reputation = self.total_reputation as u128;
+                    let tokens = self.total_staked;
+                    clamp(
+                        reputation.saturating_mul(reputation_percent as u128) / 100
+                            + tokens.saturating_mul(100 - reputation_percent as u128) / 100,
+                    )
+                }
+            }
+        }
+
+        fn to_balance(value: U256) -> Balance {
+            value.try_into().unwrap_or(Balance::MAX)
+        }
+
+        fn update_proposal_status(&self, proposal: &mut Proposal) -> Result<()> {
+            if let Some(status) = self.tally_outcome(proposal) {
+                Self::set_status(proposal, status)?;
+            }
+            Ok(())
+        }
+
+        /// Move a proposal to a new status. Every transition after creation goes through
+        /// here, executed, cancelled, vetoed, rejected and expired proposals are final.
+        fn set_status(proposal: &mut Proposal, status: ProposalStatus) -> Result<()> {
+            use ProposalStatus::*;
+            let allowed = matches!(
+                (proposal.status, status),
+                (Draft, Active)
+                    | (Draft | Active, Cancelled)
+                    | (Active, Passed | Rejected | Expired)
+                    // Overturned by a dispute
+                    | (Passed, Executed | Vetoed | Rejected)
+            );
+            if !allowed {
+                return Err(Error::InvalidStatusTransition);
+            }
+            proposal.status = status;
+            Ok(())
+        }
+
+        /// A council is small, free of duplicates and made of members
+        fn validate_council(&self, council: &[H160]) -> Result<()> {
+            if council.len() > MAX_COUNCIL_SIZE as usize {
+                return Err(Error::InvalidCouncil);
+            }
+            for (i, member) in council.iter().enumerate() {
+                if !self.members.contains(*member) || council[..i].contains(member) {
+                    return Err(Error::InvalidCouncil);
+                }
+            }
+            Ok(())
+        }
+
+        /// The council, empty once the term of an elected one ended
+        fn sitting_council(&self) -> Vec<H160> {
+            let now = self.env().block_number() as u64;
+            if self.council_term_end.is_some_and(|end| now >= end) {
+                return Vec::new();
+            }
+            self.council.get_or_default()
+        }
+
+        /// Vacate the seats of an elected council whose term ended
+        fn end_council_term(&mut self) {
+            let now = self.env().block_number() as u64;
+            if self.council_term_end.is_none_or(|end| now < end) {
+                return;
+            }
+            self.council_term_end = None;
+            let council = self.council.get_or_default();
+            self.council.set(&Vec::new());
+
+            Self::env().emit_event(CouncilTermEnded { council });
+        }
+
+        /// Seat an elected council for one term
+        fn seat_council(&mut self, council: Vec<H160>, election_id: Option<u32>) {
+            let now = self.env().block_number() as u64;
+            let ends_at = now + self.council_term;
+            self.council.set(&council);
+            self.council_term_end = Some(ends_at);
+            self.record_council_term(CouncilTerm {
+                council: council.clone(),
+                proposal_id: election_id,
+                seated_at: now,
+                ends_at: Some(ends_at),
+            });
+
+            Self::env().emit_event(CouncilSeated {
+                election_id,
+                council,
+                ends_at,
+            });
+        }
+
+        fn record_council_term(&mut self, term: CouncilTerm) {
+            self.council_terms.insert(self.council_term_count, &term);
+            self.council_term_count += 1;
+        }
+
+        /// Bounds of the core voting parameters, checked on proposal and again on execution
+        fn validate_config_update(&self, update: &ConfigUpdate) -> Result<()> {
+            let valid = match *update {
+                ConfigUpdate::VotingPeriod(blocks) => (1..=MAX_VOTING_PERIOD).contains(&blocks),
+                ConfigUpdate::MinVotesRequired(votes) => (1..=self.member_count).contains(&votes),
+                ConfigUpdate::PassThreshold(percent) => (50..100).contains(&percent),
+                ConfigUpdate::ChainId(chain_id) => chain_id > 0,
+                ConfigUpdate::RunoffPeriod(blocks) => blocks <= MAX_VOTING_PERIOD,
+                ConfigUpdate::QuorumMode(QuorumMode::Votes) => true,
+                ConfigUpdate::QuorumMode(QuorumMode::PowerPercent(percent)) => {
+                    (1..=100).contains(&percent)
+                }
+            };
+            if !valid {
+                return Err(Error::InvalidConfig);
+            }
+            Ok(())
+        }
+
+        /// Voting rules of a `ConfigureProposalType` proposal, within the global bounds
+        fn validate_type_config(&self, config: &TypeConfig) -> Result<()> {
+            self.validate_config_update(&ConfigUpdate::VotingPeriod(config.voting_period))?;
+            self.validate_config_update(&ConfigUpdate::MinVotesRequired(
+                config.min_votes_required,
+            ))?;
+            self.validate_config_update(&ConfigUpdate::PassThreshold(config.pass_threshold))
+        }
+
+        /// Whether `votes` exceed `pass_threshold` percent of `total_votes`
+        fn exceeds_threshold(pass_threshold: u8, votes: u32, total_votes: u32) -> bool {
+            votes as u64 * 100 > total_votes as u64 * pass_threshold as u64
+        }
+
+        /// Status the votes cast so far decide, `None` while voting stays open
+        fn tally_outcome(&self, proposal: &Proposal) -> Option<ProposalStatus> {
+            let total_votes = Self::total_votes(proposal);
+            let rules = self.type_config(&proposal.proposal_type);
 
-            for id in 1..self.next_proposal_id {
-                if let Some(proposal) = self.proposals.get(&id) {
-                    if proposal.status == ProposalStatus::Active
-                        && self.env().block_number() as u64 <= proposal.voting_deadline
+            if total_votes < self.quorum(proposal) {
+                return None;
+            }
+            match proposal.proposal_type {
+                // Every option may still win a seat until voting ends
+                ProposalType::Election { .. } => None,
+                ProposalType::MultipleChoice | ProposalType::BudgetVote { .. } => {
+                    // Find the option with most votes
+                    let max_votes = proposal.votes.iter().max().unwrap_or(&0);
+                    Self::exceeds_threshold(rules.pass_threshold, *max_votes, total_votes)
+                        .then_some(ProposalStatus::Passed)
+                }
+                _ => {
+                    // Approval by the pass threshold for money requests and governance actions
+                    if Self::exceeds_threshold(rules.pass_threshold, proposal.votes[0], total_votes)
+                    {
+                        Some(ProposalStatus::Passed)
+                    } else {
+                        Some(ProposalStatus::Rejected)
+                    }
+                }
+            }
+        }
+    }
+
+    impl Dao for SimpleDao {
+        #[ink(message, payable, selector = 0xf9fb13d3)]
+        fn create_proposal(
+            &mut self,
+            name: String,
+            description: String,
+            proposal_type: ProposalType,
+            options: Vec<String>,
+            amount: Option<Balance>,
+        ) -> Result<u32> {
+            self.ensure_can_propose(self.env().caller())?;
+            self.insert_proposal(
+                self.env().caller(),
+                name,
+                description,
+                proposal_type,
+                options,
+                amount,
+                MoneyRequestTerms::default(),
+                ProposalStatus::Active,
+            )
+        }
+
+        #[ink(message, selector = 0x083be260)]
+        fn vote(&mut self, proposal_id: u32, option: u32) -> Result<()> {
+            self.cast_vote(self.env().caller(), proposal_id, option)
+        }
+
+        #[ink(message, selector = 0xde5bbbbc)]
+        fn execute_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            self.ensure_not_entered()?;
+            let caller: H160 = self.env().caller();
+            let mut proposal = self.executable_proposal(proposal_id)?;
+            self.ensure_may_execute(caller, &proposal.proposal_type)?;
+
+            // Mark as executed before any external call
+            Self::set_status(&mut proposal, ProposalStatus::Executed)?;
+            self.proposals.insert(proposal_id, &proposal);
+            self.track_pending_request(&proposal, false);
+            let config_change = Self::is_config_change(&proposal.proposal_type)
+                .then(|| proposal.proposal_type.clone());
+
+            match proposal.proposal_type {
+                ProposalType::MultipleChoice | ProposalType::Election { .. } => {
+                    return Err(Error::InvalidProposalType)
+                }
+                ProposalType::MoneyRequest => {
+                    let amount = proposal.amount.unwrap_or(0);
+                    self.ensure_within_request_cap(proposal.asset, amount)?;
+                    if let Some(envelope) = &proposal.envelope {
+                        self.spend_budget(envelope, proposal.asset, amount)?;
+
+                        Self::env().emit_event(BudgetSpent {
+                            proposal_id,
+                            envelope: envelope.clone(),
+                            amount,
+                        });
+                    }
+                    if let Some(duration) = proposal.stream_duration {
+                        self.open_stream(&proposal, amount, duration)?;
+                    } else if !proposal.milestones.is_empty() {
+                        self.open_escrow(&proposal, amount)?;
+                    } else if !proposal.tranches.is_empty() {
+                        self.open_grant(&proposal, amount)?;
+                    } else {
+                        self.credit_payout(proposal.asset, Self::payee_of(&proposal), amount)?;
+                    }
+                }
+                ProposalType::BudgetVote { .. } => {
+                    let amount = Self::requested_amount(&proposal);
+                    if amount > 0 {
+                        self.ensure_within_request_cap(None, amount)?;
+                        self.credit_payout(None, Self::payee_of(&proposal), amount)?;
+                    }
+                }
+                ProposalType::RegisterAsset(asset) => {
+                    let mut assets = self.registered_assets.get_or_default();
+                    if assets.contains(&asset) {
+                        return Err(Error::AssetAlreadyRegistered);
+                    }
+                    assets.push(asset);
+                    self.registered_assets.set(&assets);
+
+                    Self::env().emit_event(AssetRegistered { asset });
+                }
+                ProposalType::CancelStream(stream_id) => self.cancel_stream(stream_id)?,
+                ProposalType::CancelEscrow(escrow_id) => self.cancel_escrow(escrow_id)?,
+                ProposalType::RecurringPayment {
+                    recipient,
+                    asset,
+                    amount,
+                    interval,
+                    end_block,
+                } => {
+                    let payment_id = self.next_recurring_payment_id;
+                    let payment = RecurringPayment {
+                        id: payment_id,
+                        proposal_id,
+                        recipient,
+                        asset,
+                        amount,
+                        interval,
+                        next_payment_block: self.env().block_number() as u64 + interval,
+                        end_block,
+                    };
+
+                    self.recurring_payments.insert(payment_id, &payment);
+                    let mut active = self.active_recurring_payments.get_or_default();
+                    active.push(payment_id);
+                    self.active_recurring_payments.set(&active);
+                    self.next_recurring_payment_id += 1;
+
+                    Self::env().emit_event(RecurringPaymentCreated {
+                        payment_id,
+                        recipient,
+                        amount,
+                        interval,
+                        end_block,
+                    });
+                }
+                ProposalType::SetProposalDeposit(amount) => self.proposal_deposit = amount,
+                ProposalType::SetRateLimit(limit) => self.rate_limit = limit,
+                // Settled when the dispute's voting closes
+                ProposalType::Dispute(_) => return Err(Error::InvalidProposalType),
+                ProposalType::ConfigureJury { kind, size } => {
+                    if size == 0 {
+                        self.jury_sizes.remove(kind);
+                    } else {
+                        self.jury_sizes.insert(kind, &size);
+                    }
+                }
+                ProposalType::ConfigureVoteDecay {
+                    kind,
+                    bonus_percent,
+                } => {
+                    if bonus_percent == 0 {
+                        self.vote_decays.remove(kind);
+                    } else {
+                        self.vote_decays.insert(kind, &bonus_percent);
+                    }
+                }
+                ProposalType::ConfigureCouncilTerms { term, seats } => {
+                    self.council_term = term;
+                    self.council_seats = seats;
+                }
+                ProposalType::ConfigureDisputes { bond, arbiter } => {
+                    self.dispute_bond = bond;
+                    self.arbiter = arbiter;
+                }
+                ProposalType::FreezeMember(member) => {
+                    self.frozen.insert(member, &());
+                    // A transfer the compromised key may have started must not go through
+                    self.pending_transfers.remove(member);
+
+                    Self::env().emit_event(MemberFrozen {
+                        proposal_id,
+                        member,
+                    });
+                }
+                ProposalType::UnfreezeMember { member, migrate_to } => {
+                    if !self.frozen.contains(member) {
+                        return Err(Error::NotFrozen);
+                    }
+                    // A migrating membership stays frozen until the new address accepts
+                    if let Some(to) = migrate_to {
+                        self.pending_transfers.insert(member, &to);
+                    } else {
+                        self.frozen.remove(member);
+                    }
+
+                    Self::env().emit_event(MemberUnfrozen {
+                        proposal_id,
+                        member,
+                        migrate_to,
+                    });
+                }
+                ProposalType::SetMaxRequestPercent(percent) => self.max_request_percent = percent,
+                ProposalType::ConfigureTips {
+                    cap,
+                    approvals,
+                    budget,
+                    period,
+                } => {
+                    self.tip_cap = cap;
+                    self.tip_approvals_required = approvals;
+                    self.tip_budget = budget;
+                    self.tip_period = period;
+                }
+                ProposalType::GrantProgress(grant_id) => self.release_grant_tranche(grant_id)?,
+                ProposalType::ConfigureVoteRebates {
+                    rate,
+                    cap_per_proposal,
+                } => {
+                    self.vote_rebate = rate;
+                    self.max_rebate_per_proposal = cap_per_proposal;
+                }
+                ProposalType::AllocateBudget {
+                    envelope,
+                    asset,
+                    amount,
+                } => {
+                    let mut budget = match self.budgets.get(&envelope) {
+                        Some(budget) => budget,
+                        None => {
+                            let mut names = self.budget_names.get_or_default();
+                            names.push(envelope.clone());
+                            self.budget_names.set(&names);
+                            Budget {
+                                name: envelope.clone(),
+                                asset,
+                                allocated: 0,
+                                spent: 0,
+                            }
+                        }
+                    };
+                    if budget.asset != asset {
+                        return Err(Error::BudgetAssetMismatch);
+                    }
+
+                    budget.allocated += amount;
+                    self.budgets.insert(&envelope, &budget);
+
+                    Self::env().emit_event(BudgetAllocated {
+                        envelope,
+                        amount,
+                        allocated: budget.allocated,
+                    });
+                }
+                ProposalType::CancelRecurringPayment(payment_id) => {
+                    let mut active = self.active_recurring_payments.get_or_default();
+                    if !active.contains(&payment_id) {
+                        return Err(Error::RecurringPaymentNotFound);
+                    }
+                    active.retain(|id| *id != payment_id);
+                    self.active_recurring_payments.set(&active);
+
+                    Self::env().emit_event(RecurringPaymentCancelled { payment_id });
+                }
+                ProposalType::AddMember(member) => {
+                    if self.members.contains(member) {
+                        return Err(Error::AlreadyMember);
+                    }
+                    let expires_at = self.env().block_number() as u64 + self.voting_period;
+                    let invitation = Invitation {
+                        proposal_id,
+                        tokens: proposal.amount.unwrap_or(0),
+                        expires_at,
+                    };
+                    self.invitations.insert(member, &invitation);
+
+                    Self::env().emit_event(MemberInvited { member, expires_at });
+                }
+                ProposalType::ConfigureMembership {
+                    endorsements,
+                    initial_tokens,
+                } => {
+                    self.membership_endorsements = endorsements;
+                    self.membership_grant = initial_tokens;
+                }
+                ProposalType::ConfigureMembershipTerm { term, renewal_fee } => {
+                    // Expiries left over from before the term was switched off are stale
+                    let reenabled = self.membership_term == 0;
+                    self.membership_term = term;
+                    self.renewal_fee = renewal_fee;
+
+                    // Members without a current expiry get a full term from now on
+                    let expires_at = self.env().block_number() as u64 + term;
+                    for member in self.member_list() {
+                        if reenabled || !self.membership_expiry.contains(member) {
+                            self.membership_expiry.insert(member, &expires_at);
+                        }
+                    }
+                    if reenabled {
+                        self.lapsed_members.set(&Vec::new());
+                    }
+                }
+                ProposalType::RemoveMember(member) => {
+                    if !self.members.contains(member) {
+                        return Err(Error::NotMember);
+                    }
+                    if self.member_count == 1 {
+                        return Err(Error::LastMember);
+                    }
+                    let tokens = self.remove_member(member);
+                    self.kicked.insert(member, &());
+                    match self.kick_policy {
+                        KickPolicy::Burn => {}
+                        KickPolicy::Redistribute => self.redistribute_tokens(tokens)?,
+                        KickPolicy::Escrow => {
+                            let escrowed = self.escrowed_tokens.get(member).unwrap_or(0);
+                            let escrowed = escrowed.checked_add(tokens).ok_or(Error::Overflow)?;
+                            self.escrowed_tokens.insert(member, &escrowed);
+                        }
+                    }
+
+                    Self::env().emit_event(MemberKicked {
+                        member,
+                        tokens,
+                        policy: self.kick_policy,
+                    });
+                }
+                ProposalType::SetKickPolicy(policy) => self.kick_policy = policy,
+                ProposalType::AddObserver(observer) => {
+                    if self.members.contains(observer) {
+                        return Err(Error::AlreadyMember);
+                    }
+                    let mut observers = self.observers.get_or_default();
+                    if observers.contains(&observer) {
+                        return Err(Error::AlreadyObserver);
+                    }
+                    observers.push(observer);
+                    self.observers.set(&observers);
+
+                    Self::env().emit_event(ObserverAdded { observer });
+                }
+                ProposalType::RemoveObserver(observer) => {
+                    let mut observers = self.observers.get_or_default();
+                    if !observers.contains(&observer) {
+                        return Err(Error::NotObserver);
+                    }
+                    observers.retain(|o| *o != observer);
+                    self.observers.set(&observers);
+
+                    Self::env().emit_event(ObserverRemoved { observer });
+                }
+                ProposalType::SetAllowlisted { account, allowed } => {
+                    if allowed {
+                        self.allowlist.insert(account, &());
+                        self.kicked.remove(account);
+                    } else {
+                        self.allowlist.remove(account);
+                    }
+
+                    Self::env().emit_event(AllowlistUpdated { account, allowed });
+                }
+                ProposalType::SetJoinFee(fee) => self.join_fee = fee,
+                ProposalType::ConfigureBuyIn {
+                    tokens,
+                    price,
+                    period,
+                    cap,
+                } => {
+                    self.buy_in_tokens = tokens;
+                    self.buy_in_price = price;
+                    self.buy_in_period = period;
+                    self.buy_in_cap = cap;
+                    self.buy_in_period_start = self.env().block_number() as u64;
+                    self.buy_in_issued = 0;
+                }
+                ProposalType::Slash {
+                    member,
+                    amount,
+                    reason_hash,
+                    to_treasury,
+                } => {
+                    if !self.members.contains(member) {
+                        return Err(Error::NotMember);
+                    }
+                    let amount = self.slash_tokens(member, amount, to_treasury)?;
+
+                    let mut slashes = self.slashes.get(member).unwrap_or_default();
+                    slashes.push(SlashRecord {
+                        proposal_id,
+                        amount,
+                        reason_hash,
+                        to_treasury,
+                        block: self.env().block_number() as u64,
+                    });
+                    self.slashes.insert(member, &slashes);
+
+                    Self::env().emit_event(MemberSlashed {
+                        member,
+                        amount,
+                        reason_hash,
+                        to_treasury,
+                    });
+                }
+                ProposalType::SlashReputation { member, amount } => {
+                    if !self.members.contains(member) {
+                        return Err(Error::NotMember);
+                    }
+                    let reputation = self
+                        .reputation
+                        .get(member)
+                        .unwrap_or(0)
+                        .saturating_sub(amount);
+                    self.set_reputation(member, reputation);
+
+                    Self::env().emit_event(ReputationChanged { member, reputation });
+                }
+                ProposalType::SetVoteWeightSource(source) => self.vote_weight_source = source,
+                ProposalType::SetResignationPolicy(policy) => self.resignation_policy = policy,
+                ProposalType::TokenMint { recipient, amount } => {
+                    self.mint_tokens(recipient, amount)?
+                }
+                ProposalType::ConfigureEmission {
+                    amount,
+                    epoch_length,
+                    decay_percent,
+                } => {
+                    self.emission_amount = amount;
+                    self.emission_epoch_length = epoch_length;
+                    self.emission_decay_percent = decay_percent;
+                    self.next_emission_block = self.env().block_number() as u64 + epoch_length;
+                }
+                ProposalType::Buyback {
+                    market,
+                    payment,
+                    min_tokens,
+                } => {
+                    self.ensure_within_request_cap(None, payment)?;
+                    if self.free_funds(None) < payment {
+                        return Err(Error::InsufficientBalance);
+                    }
+
+                    let token_market: ink::contract_ref_from_path!(TokenMarket) = market.into();
+                    let tokens = token_market.quote(payment);
+                    if tokens < min_tokens {
+                        return Err(Error::SlippageExceeded);
+                    }
+                    self.burn_from(market, tokens)?;
+                    self.record_outflow(payment);
+                    self.release_funds(None, market, payment)?;
+
+                    Self::env().emit_event(BuybackExecuted {
+                        market,
+                        payment,
+                        burned: tokens,
+                    });
+                }
+                ProposalType::SetWrappedToken(token) => {
+                    if self.wrapped_total > 0 {
+                        return Err(Error::WrappedDepositsOutstanding);
+                    }
+                    self.wrapped_token = token;
+                }
+                ProposalType::SetAirdropRoot(root) => {
+                    self.airdrop_round += 1;
+                    self.airdrop_root = (root != [0; 32]).then_some(root);
+
+                    Self::env().emit_event(AirdropRootSet {
+                        round: self.airdrop_round,
+                        root,
+                    });
+                }
+                ProposalType::AwardTokens { recipient, amount } => {
+                    if self.treasury_tokens < amount {
+                        return Err(Error::InsufficientBalance);
+                    }
+                    // Pool tokens are already part of the supply, minting re-adds them
+                    self.treasury_tokens -= amount;
+                    self.total_supply -= amount;
+                    self.mint_tokens(recipient, amount)?;
+                }
+                ProposalType::GrantVesting {
+                    recipient,
+                    amount,
+                    cliff,
+                    duration,
+                } => {
+                    if !self.members.contains(recipient) {
+                        return Err(Error::NotMember);
+                    }
+                    if let Some(schedule) = self.vesting.get(recipient) {
+                        if schedule.claimed < schedule.total {
+                            return Err(Error::VestingActive);
+                        }
+                    }
+                    let schedule = VestingSchedule {
+                        total: amount,
+                        claimed: 0,
+                        start_block: self.env().block_number() as u64,
+                        cliff,
+                        duration,
+                    };
+                    self.vesting.insert(recipient, &schedule);
+
+                    Self::env().emit_event(VestingGranted {
+                        recipient,
+                        amount,
+                        cliff,
+                        duration,
+                    });
+                }
+                ProposalType::SetVestingVotePower(enabled) => self.vested_vote_power = enabled,
+                ProposalType::SetUnbondingPeriod(blocks) => self.unbonding_period = blocks,
+                ProposalType::SetUpgradeTimelock(blocks) => self.upgrade_timelock = blocks,
+                ProposalType::SetPauseQuorum(members) => self.pause_quorum = members,
+                ProposalType::ElectCouncil(council) => {
+                    self.validate_council(&council)?;
+                    self.council.set(&council);
+                    // Sits until replaced, whatever the term of elected councils
+                    self.council_term_end = None;
+                    self.record_council_term(CouncilTerm {
+                        council: council.clone(),
+                        proposal_id: Some(proposal_id),
+                        seated_at: self.env().block_number() as u64,
+                        ends_at: None,
+                    });
+
+                    Self::env().emit_event(CouncilElected {
+                        proposal_id,
+                        council,
+                    });
+                }
+                ProposalType::Unpause => {
+                    if !self.paused {
+                        return Err(Error::NotPaused);
+                    }
+                    self.paused = false;
+                    self.pause_round += 1;
+                    self.pause_call_count = 0;
+
+                    Self::env().emit_event(DaoUnpaused { proposal_id });
+                }
+                ProposalType::ConfigChange(update) => {
+                    self.validate_config_update(&update)?;
+                    match update {
+                        ConfigUpdate::VotingPeriod(blocks) => self.voting_period = blocks,
+                        ConfigUpdate::MinVotesRequired(votes) => self.min_votes_required = votes,
+                        ConfigUpdate::PassThreshold(percent) => self.pass_threshold = percent,
+                        ConfigUpdate::ChainId(chain_id) => self.chain_id = chain_id,
+                        ConfigUpdate::RunoffPeriod(blocks) => self.runoff_period = blocks,
+                        ConfigUpdate::QuorumMode(mode) => self.quorum_mode = mode,
+                    }
+                }
+                ProposalType::ConfigureProposalType { kind, config } => match config {
+                    Some(config) => {
+                        self.validate_type_config(&config)?;
+                        self.type_configs.insert(kind, &config);
+                    }
+                    None => self.type_configs.remove(kind),
+                },
+                ProposalType::XcmExecute(message) => {
+                    let message: VersionedXcm<()> = Self::decode_xcm(&message)?;
+                    let held = self.held_funds(None);
+                    let weight = self
+                        .env()
+                        .xcm_weigh(&message)
+                        .map_err(|_| Error::XcmFailed)?;
+                    self.env()
+                        .xcm_execute(&message, weight)
+                        .map_err(|_| Error::XcmFailed)?;
+                    self.settle_xcm_outflow(proposal_id, held)?;
+                }
+                ProposalType::XcmSend { dest, message } => {
+                    let dest: VersionedLocation = Self::decode_xcm(&dest)?;
+                    let message: VersionedXcm<()> = Self::decode_xcm(&message)?;
+                    let held = self.held_funds(None);
+                    self.env()
+                        .xcm_send(&dest, &message)
+                        .map_err(|_| Error::XcmFailed)?;
+                    self.settle_xcm_outflow(proposal_id, held)?;
+                }
+                ProposalType::Upgrade(code_hash) => {
+                    // The new code must keep the storage layout of this one
+                    self.env()
+                        .set_code_hash(&code_hash)
+                        .map_err(|_| Error::UpgradeFailed)?;
+                    self.code_version = self.code_version.saturating_add(1);
+
+                    Self::env().emit_event(CodeUpgraded {
+                        proposal_id,
+                        code_hash,
+                        version: self.code_version,
+                    });
+                }
+                ProposalType::SetMemberOnlyTransfers(restricted) => {
+                    self.member_only_transfers = restricted
+                }
+                ProposalType::SetMaxMembers(limit) => {
+                    if limit < self.member_count {
+                        return Err(Error::InvalidMemberLimit);
+                    }
+                    self.max_members = limit;
+                }
+                ProposalType::CharterCommittee {
+                    name,
+                    members,
+                    envelope,
+                    quorum,
+                    max_request,
+                } => {
+                    self.charter_committee(name, members, envelope, quorum, max_request);
+                }
+                ProposalType::SetRandomnessOracle(oracle) => self.randomness_oracle = oracle,
+                ProposalType::SetExecutor(executor) => self.executor = executor,
+                ProposalType::RegisterHook {
+                    contract,
+                    ref_time_limit,
+                } => {
+                    let mut hooks = self.hooks.get_or_default();
+                    if hooks.len() >= MAX_HOOKS as usize
+                        || hooks.iter().any(|hook| hook.contract == contract)
                     {
-                        active_proposals.push(id);
+                        return Err(Error::InvalidHook);
+                    }
+                    hooks.push(Hook {
+                        contract,
+                        ref_time_limit,
+                    });
+                    self.hooks.set(&hooks);
+
+                    Self::env().emit_event(HookRegistered {
+                        contract,
+                        ref_time_limit,
+                    });
+                }
+                ProposalType::SetDaoMetadata(metadata) => {
+                    self.metadata.set(&metadata);
+
+                    Self::env().emit_event(DaoMetadataUpdated {
+                        proposal_id,
+                        dao_uri: metadata.dao_uri,
+                    });
+                }
+                ProposalType::UnregisterHook(contract) => {
+                    let mut hooks = self.hooks.get_or_default();
+                    let position = hooks
+                        .iter()
+                        .position(|hook| hook.contract == contract)
+                        .ok_or(Error::HookNotFound)?;
+                    hooks.remove(position);
+                    self.hooks.set(&hooks);
+
+                    Self::env().emit_event(HookUnregistered { contract });
+                }
+                ProposalType::DrawCommittee { .. } => self.request_draw(proposal_id)?,
+                ProposalType::Lottery { prize } => {
+                    self.reserve_funds(None, prize)?;
+                    self.request_draw(proposal_id)?;
+                }
+                ProposalType::DissolveCommittee(committee_id) => {
+                    let mut committee = self
+                        .committees
+                        .get(committee_id)
+                        .ok_or(Error::CommitteeNotFound)?;
+                    if committee.dissolved {
+                        return Err(Error::CommitteeDissolved);
+                    }
+                    committee.dissolved = true;
+                    self.committees.insert(committee_id, &committee);
+                    self.close_committee(&committee);
+
+                    Self::env().emit_event(CommitteeDissolved { committee_id });
+                }
+                ProposalType::AssignTier { account, tier } => {
+                    if !self.members.contains(account) {
+                        return Err(Error::NotMember);
+                    }
+                    let previous = self.member_tiers.get(account).unwrap_or_default();
+                    self.change_tier_count(previous, false);
+                    self.change_tier_count(tier, true);
+                    self.member_tiers.insert(account, &tier);
+
+                    Self::env().emit_event(TierChanged { account, tier });
+                }
+                ProposalType::ConfigureTier {
+                    tier,
+                    vote_weight,
+                    can_propose,
+                } => {
+                    let config = TierConfig {
+                        vote_weight,
+                        can_propose,
+                    };
+                    self.tier_configs.insert(tier, &config);
+
+                    Self::env().emit_event(TierConfigured {
+                        tier,
+                        vote_weight,
+                        can_propose,
+                    });
+                }
+                ProposalType::AssignRole { account, role } => {
+                    if !self.members.contains(account) {
+                        return Err(Error::NotMember);
                     }
+                    self.roles.insert(account, &role);
+
+                    Self::env().emit_event(RoleChanged { account, role });
+                }
+            }
+
+            if let Some(change) = config_change {
+                Self::env().emit_event(ConfigChanged {
+                    proposal_id,
+                    change,
+                });
+            }
+            Self::env().emit_event(ProposalExecuted {
+                proposal_id,
+                executor: caller,
+            });
+            self.notify_hooks(HookEvent::ProposalExecuted(proposal_id));
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0x745e9f7a)]
+        fn finalize_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalAlreadyFinalized);
+            }
+            if self.env().block_number() as u64 <= proposal.voting_deadline {
+                return Err(Error::VotingPeriodNotOver);
+            }
+
+            let status = self.deadline_outcome(&proposal);
+            Self::set_status(&mut proposal, status)?;
+            self.proposals.insert(proposal_id, &proposal);
+            self.on_voting_closed(&proposal);
+
+            if proposal.status == ProposalStatus::Expired {
+                Self::env().emit_event(ProposalExpired {
+                    proposal_id,
+                    voting_deadline: proposal.voting_deadline,
+                });
+                if self.needs_runoff(&proposal) {
+                    self.open_runoff(&proposal);
                 }
             }
 
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xba4dc5ec)]
+        fn get_proposal(&self, proposal_id: u32) -> Result<Proposal> {
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+
+            // Update status if voting period expired
+            proposal.status = self.current_status(&proposal);
+            proposal.description = self
+                .proposal_descriptions
+                .get(proposal_id)
+                .unwrap_or_default();
+
+            Ok(proposal)
+        }
+
+        #[ink(message, selector = 0x38539121)]
+        fn get_active_proposals(&self) -> Vec<u32> {
+            let mut active_proposals: Vec<u32> = (0..self.active_proposal_count)
+                .filter_map(|index| self.active_proposal_positions.get(index))
+                .filter(|id| self.is_open_for_voting(*id))
+                .collect();
+            active_proposals.sort_unstable();
+
             active_proposals
         }
 
-        #[ink(message)]
-        pub fn get_member_balance(&self, member: H160) -> Balance {
-            self.member_tokens.get(&member).unwrap_or(0)
+        #[ink(message, selector = 0x01d0e109)]
+        fn get_tally(&self, proposal_id: u32) -> Result<Tally> {
+            let proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+
+            let total_votes = Self::total_votes(&proposal);
+            let quorum = self.quorum(&proposal);
+            let quorum_percent = if quorum == 0 {
+                100
+            } else {
+                (total_votes as u64 * 100 / quorum as u64).min(100) as u8
+            };
+            let now = self.env().block_number() as u64;
+            let mut status = self.current_status(&proposal);
+            if status == ProposalStatus::Active {
+                status = self.tally_outcome(&proposal).unwrap_or(status);
+            }
+
+            Ok(Tally {
+                total_votes,
+                turnout: proposal.voter_count,
+                quorum_percent,
+                blocks_remaining: proposal.voting_deadline.saturating_sub(now),
+                status,
+                votes: proposal.votes,
+            })
         }
 
-        #[ink(message)]
-        pub fn get_members(&self) -> Vec<H160> {
-            self.members.clone()
+        #[ink(message, selector = 0x47f5deed)]
+        fn voting_power_of(&self, member: H160, proposal_id: Option<u32>) -> u32 {
+            if !self.members.contains(member) || self.is_membership_expired(member) {
+                return 0;
+            }
+            let mut snapshot_block = self.env().block_number() as u64;
+            let mut proposal = None;
+            if let Some(proposal_id) = proposal_id {
+                if self.ballots.contains((proposal_id, member))
+                    || !self.is_open_for_voting(proposal_id)
+                {
+                    return 0;
+                }
+                proposal = self.proposals.get(proposal_id);
+                if let Some(proposal) = &proposal {
+                    snapshot_block = proposal.created_at;
+                }
+            }
+            let weight = self.vote_weight_of(member, snapshot_block);
+            match proposal {
+                Some(proposal) => self.decayed_weight(weight, &proposal),
+                None => weight,
+            }
         }
 
-        #[ink(message)]
-        pub fn get_total_supply(&self) -> Balance {
+        #[ink(message, selector = 0x4efcf717)]
+        fn is_member(&self, account: H160) -> bool {
+            self.members.contains(account)
+        }
+
+        #[ink(message, selector = 0xd0707806)]
+        fn get_config(&self) -> DaoConfig {
+            DaoConfig {
+                voting_period: self.voting_period,
+                min_votes_required: self.min_votes_required,
+                pass_threshold: self.pass_threshold,
+                proposal_deposit: self.proposal_deposit,
+                max_request_percent: self.max_request_percent,
+                tip_cap: self.tip_cap,
+                tip_approvals_required: self.tip_approvals_required,
+                tip_budget: self.tip_budget,
+                tip_period: self.tip_period,
+                vote_rebate: self.vote_rebate,
+                max_rebate_per_proposal: self.max_rebate_per_proposal,
+                membership_endorsements: self.membership_endorsements,
+                membership_grant: self.membership_grant,
+                membership_term: self.membership_term,
+                renewal_fee: self.renewal_fee,
+                join_fee: self.join_fee,
+                max_members: self.max_members,
+                kick_policy: self.kick_policy,
+                resignation_policy: self.resignation_policy,
+                vote_weight_source: self.vote_weight_source,
+                member_only_transfers: self.member_only_transfers,
+                vested_vote_power: self.vested_vote_power,
+                unbonding_period: self.unbonding_period,
+                buy_in_tokens: self.buy_in_tokens,
+                buy_in_price: self.buy_in_price,
+                buy_in_period: self.buy_in_period,
+                buy_in_cap: self.buy_in_cap,
+                upgrade_timelock: self.upgrade_timelock,
+                pause_quorum: self.pause_quorum,
+                chain_id: self.chain_id,
+                runoff_period: self.runoff_period,
+                quorum_mode: self.quorum_mode,
+            }
+        }
+    }
+
+    /// Name, symbol and decimals set with `new_with_token`
+    impl PSP22Metadata for SimpleDao {
+        #[ink(message, selector = 0x3d261bd4)]
+        fn token_name(&self) -> Option<String> {
+            self.token_name.get_or_default()
+        }
+
+        #[ink(message, selector = 0x34205be5)]
+        fn token_symbol(&self) -> Option<String> {
+            self.token_symbol.get_or_default()
+        }
+
+        #[ink(message, selector = 0x7271b782)]
+        fn token_decimals(&self) -> u8 {
+            self.token_decimals
+        }
+    }
+
+    /// Governance tokens (`member_tokens`) exposed as a PSP22 token
+    impl PSP22 for SimpleDao {
+        #[ink(message, selector = 0x162df8c2)]
+        fn total_supply(&self) -> Balance {
             self.total_supply
         }
 
-        #[ink(message)]
-        pub fn is_member(&self, account: H160) -> bool {
-            self.members.contains(&account)
+        #[ink(message, selector = 0x6568382f)]
+        fn balance_of(&self, owner: H160) -> Balance {
+            self.member_tokens.get(owner).unwrap_or(0)
         }
 
-        #[ink(message)]
-        pub fn add_blocks(&self) -> u32 {
-            self.env().block_number()
+        #[ink(message, selector = 0x4d47d921)]
+        fn allowance(&self, owner: H160, spender: H160) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or(0)
         }
 
-        fn update_proposal_status(&self, proposal: &mut Proposal) {
-            let total_votes: u32 = proposal.votes.iter().sum();
+        #[ink(message, selector = 0xb20f1bbd)]
+        fn approve(
+            &mut self,
+            spender: H160,
+            value: Balance,
+        ) -> core::result::Result<(), PSP22Error> {
+            let owner: H160 = self.env().caller();
 
-            if total_votes >= self.min_votes_required {
-                match proposal.proposal_type {
-                    ProposalType::MultipleChoice => {
-                        // Find the option with most votes
-                        let max_votes = proposal.votes.iter().max().unwrap_or(&0);
-                        if *max_votes > total_votes / 2 {
-                            proposal.status = ProposalStatus::Passed;
-                        }
-                    }
-                    ProposalType::MoneyRequest => {
-                        // Simple majority for money requests
-                        if proposal.votes[0] > total_votes / 2 {
-                            proposal.status = ProposalStatus::Passed;
-                        } else {
-                            proposal.status = ProposalStatus::Rejected;
-                        }
-                    }
-                }
+            if self.soulbound {
+                return Err(PSP22Error::Custom(String::from("SoulboundToken")));
+            }
+            if self.frozen.contains(owner) {
+                return Err(PSP22Error::Custom(String::from("AccountFrozen")));
+            }
+            self.allowances.insert((owner, spender), &value);
+
+            Self::env().emit_event(Approval {
+                owner,
+                spender,
+                amount: value,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xdb20f9f5)]
+        fn transfer(
+            &mut self,
+            to: H160,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> core::result::Result<(), PSP22Error> {
+            let from: H160 = self.env().caller();
+            self.move_tokens(from, to, value)
+        }
+
+        #[ink(message, selector = 0x54b3c76e)]
+        fn transfer_from(
+            &mut self,
+            from: H160,
+            to: H160,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> core::result::Result<(), PSP22Error> {
+            let spender: H160 = self.env().caller();
+
+            // A frozen key can't spend the allowances it was given either
+            if self.frozen.contains(spender) {
+                return Err(PSP22Error::Custom(String::from("AccountFrozen")));
+            }
+            let allowance = self.allowances.get((from, spender)).unwrap_or(0);
+            if allowance < value {
+                return Err(PSP22Error::InsufficientAllowance);
+            }
+
+            self.move_tokens(from, to, value)?;
+            self.allowances
+                .insert((from, spender), &(allowance - value));
+
+            Self::env().emit_event(Approval {
+                owner: from,
+                spender,
+                amount: allowance - value,
+            });
+
+            Ok(())
+        }
+    }
+
+    /// Soulbound membership tokens exposed as a PSP34 collection, ids are `Id::U32`
+    impl PSP34 for SimpleDao {
+        #[ink(message, selector = 0xffa27a5f)]
+        fn collection_id(&self) -> Id {
+            Id::Bytes(self.env().address().as_bytes().to_vec())
+        }
+
+        #[ink(message, selector = 0xcde7e55f)]
+        fn balance_of(&self, owner: H160) -> u32 {
+            self.membership_nfts.contains(owner) as u32
+        }
+
+        #[ink(message, selector = 0x1168624d)]
+        fn owner_of(&self, id: Id) -> Option<H160> {
+            match id {
+                Id::U32(token_id) => self.membership_nft_owners.get(token_id),
+                _ => None,
             }
         }
+
+        #[ink(message, selector = 0x4790f55a)]
+        fn allowance(&self, _owner: H160, _operator: H160, _id: Option<Id>) -> bool {
+            false
+        }
+
+        #[ink(message, selector = 0x1932a8b0)]
+        fn approve(
+            &mut self,
+            _operator: H160,
+            _id: Option<Id>,
+            _approved: bool,
+        ) -> core::result::Result<(), PSP34Error> {
+            Err(PSP34Error::Custom(String::from("SoulboundToken")))
+        }
+
+        #[ink(message, selector = 0x3128d61b)]
+        fn transfer(
+            &mut self,
+            _to: H160,
+            _id: Id,
+            _data: Vec<u8>,
+        ) -> core::result::Result<(), PSP34Error> {
+            Err(PSP34Error::Custom(String::from("SoulboundToken")))
+        }
+
+        #[ink(message, selector = 0x628413fe)]
+        fn total_supply(&self) -> Balance {
+            self.member_count as Balance
+        }
     }
 }
 
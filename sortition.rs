@@ -0,0 +1,72 @@
+//! Random draws among members: the oracle the randomness comes from and the selection it
+//! drives. Draws happen in two steps, a passed proposal requests randomness and fixes the
+//! candidates, and a later `complete_draw` consumes it, so nobody knows the outcome when the
+//! request is made and nobody can reshuffle the candidates once the randomness is public.
+
+use ink::env::hash::{HashOutput, Keccak256};
+use ink::prelude::vec::Vec;
+use ink::H160;
+
+/// Source of verifiable randomness, e.g. a VRF oracle contract
+#[ink::trait_definition]
+pub trait RandomnessOracle {
+    /// Ask for a random value to be fulfilled in a later block, returns the request id
+    #[ink(message)]
+    fn request_randomness(&mut self, seed: [u8; 32]) -> u64;
+
+    /// Random value of a request, `None` until the oracle fulfilled it
+    #[ink(message)]
+    fn randomness(&self, request_id: u64) -> Option<[u8; 32]>;
+}
+
+/// Seed of the randomness request made for a proposal
+pub fn seed(proposal_id: u32, block: u64) -> [u8; 32] {
+    let mut seed = [0u8; 32];
+    seed[..4].copy_from_slice(&proposal_id.to_le_bytes());
+    seed[4..12].copy_from_slice(&block.to_le_bytes());
+    seed
+}
+
+/// Up to `count` distinct candidates picked by `randomness`, in draw order
+pub fn draw(randomness: [u8; 32], candidates: &[H160], count: usize) -> Vec<H160> {
+    let mut pool = candidates.to_vec();
+    let count = count.min(pool.len());
+
+    // Partial Fisher-Yates shuffle, each pick takes a fresh word of randomness
+    for i in 0..count {
+        let remaining = (pool.len() - i) as u64;
+        let j = i + (word(&randomness, i as u32) % remaining) as usize;
+        pool.swap(i, j);
+    }
+    pool.truncate(count);
+    pool
+}
+
+/// Up to `count` of the candidates `eligible` still accepts, picked by `randomness`. All
+/// candidates are ranked before any is dropped, so one leaving can't change who else is picked.
+pub fn draw_eligible(
+    randomness: [u8; 32],
+    candidates: &[H160],
+    count: usize,
+    eligible: impl Fn(&H160) -> bool,
+) -> Vec<H160> {
+    draw(randomness, candidates, candidates.len())
+        .into_iter()
+        .filter(|candidate| eligible(candidate))
+        .take(count)
+        .collect()
+}
+
+/// Word `index` of the stream derived from `randomness`
+fn word(randomness: &[u8; 32], index: u32) -> u64 {
+    let mut input = [0u8; 36];
+    input[..32].copy_from_slice(randomness);
+    input[32..].copy_from_slice(&index.to_le_bytes());
+
+    let mut output = <Keccak256 as HashOutput>::Type::default();
+    ink::env::hash_bytes::<Keccak256>(&input, &mut output);
+
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&output[..8]);
+    u64::from_le_bytes(bytes)
+}
@@ -0,0 +1,176 @@
+//! Fixtures for off-chain tests: a builder for the DAO under test and helpers for the steps
+//! most scenarios share. Only built with `std`, where the off-chain environment exists.
+
+use ink::env::test;
+use ink::env::DefaultEnvironment;
+use ink::{H160, U256};
+
+use crate::{Dao, ProposalType, SimpleDao, TokenConfig};
+
+/// Test account `n`, the address made of the byte `n`
+pub fn account(n: u8) -> H160 {
+    H160::from([n; 20])
+}
+
+/// Three member candidates and an account that stays outside the DAO
+pub fn create_accounts() -> (H160, H160, H160, H160) {
+    (account(1), account(2), account(3), account(4))
+}
+
+pub fn set_caller(caller: H160) {
+    test::set_caller(caller);
+}
+
+pub fn advance_block(blocks: u64) {
+    for _ in 0..blocks {
+        test::advance_block::<DefaultEnvironment>();
+    }
+}
+
+/// Set the contract's native balance
+pub fn fund_treasury(amount: u128) {
+    test::set_contract_balance(test::callee(), U256::from(amount));
+}
+
+/// Attach native value to the next call
+pub fn transfer_in(from: H160, amount: u128) {
+    test::set_contract_balance(from, U256::from(1_000_000));
+    test::transfer_in(U256::from(amount));
+}
+
+/// Create, pass and execute a governance action. Needs a DAO where the caller's vote
+/// alone passes the proposal.
+pub fn pass_proposal(dao: &mut SimpleDao, proposal_type: ProposalType) -> u32 {
+    let proposal_id = dao
+        .create_proposal(
+            "Governance action".to_string(),
+            "Passed in tests".to_string(),
+            proposal_type,
+            vec!["Approve".to_string()],
+            None,
+        )
+        .unwrap();
+    dao.vote(proposal_id, 0).unwrap();
+    dao.execute_proposal(proposal_id).unwrap();
+    proposal_id
+}
+
+/// Same as `pass_proposal` for actions executed only after the timelock
+pub fn pass_sensitive_proposal(dao: &mut SimpleDao, proposal_type: ProposalType) -> u32 {
+    let proposal_id = dao
+        .create_proposal(
+            "Sensitive action".to_string(),
+            "Passed in tests".to_string(),
+            proposal_type,
+            vec!["Approve".to_string()],
+            None,
+        )
+        .unwrap();
+    dao.vote(proposal_id, 0).unwrap();
+    assert_eq!(
+        dao.execute_proposal(proposal_id),
+        Err(crate::Error::TimelockActive)
+    );
+    advance_block(dao.get_upgrade_timelock());
+    dao.execute_proposal(proposal_id).unwrap();
+    proposal_id
+}
+
+/// Open `count` yes/no proposals as the current caller, returning their ids
+pub fn create_proposals(dao: &mut SimpleDao, count: u32) -> Vec<u32> {
+    (0..count)
+        .map(|n| {
+            dao.create_proposal(
+                format!("Proposal {n}"),
+                "Created in tests".to_string(),
+                ProposalType::MultipleChoice,
+                vec!["Yes".to_string(), "No".to_string()],
+                None,
+            )
+            .unwrap()
+        })
+        .collect()
+}
+
+/// Sets up the DAO under test. Defaults to a single founding member `account(1)`, a
+/// supply of 1000, a voting period of 10 blocks and a quorum of one vote.
+pub struct DaoBuilder {
+    members: Vec<H160>,
+    total_supply: u128,
+    voting_period: u64,
+    min_votes_required: u32,
+    token: TokenConfig,
+    treasury: u128,
+}
+
+impl Default for DaoBuilder {
+    fn default() -> Self {
+        Self {
+            members: vec![account(1)],
+            total_supply: 1000,
+            voting_period: 10,
+            min_votes_required: 1,
+            token: TokenConfig::default(),
+            treasury: 0,
+        }
+    }
+}
+
+impl DaoBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Founding members `account(1)` to `account(count)`
+    pub fn member_count(mut self, count: u8) -> Self {
+        self.members = (1..=count).map(account).collect();
+        self
+    }
+
+    pub fn members(mut self, members: Vec<H160>) -> Self {
+        self.members = members;
+        self
+    }
+
+    pub fn total_supply(mut self, total_supply: u128) -> Self {
+        self.total_supply = total_supply;
+        self
+    }
+
+    pub fn voting_period(mut self, voting_period: u64) -> Self {
+        self.voting_period = voting_period;
+        self
+    }
+
+    pub fn min_votes_required(mut self, min_votes_required: u32) -> Self {
+        self.min_votes_required = min_votes_required;
+        self
+    }
+
+    pub fn token(mut self, token: TokenConfig) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Native balance of the treasury once built
+    pub fn treasury(mut self, amount: u128) -> Self {
+        self.treasury = amount;
+        self
+    }
+
+    /// Deploy the DAO with the first member as the caller
+    pub fn build(self) -> SimpleDao {
+        set_caller(self.members[0]);
+        let dao = SimpleDao::new_with_token(
+            self.members,
+            self.total_supply,
+            self.voting_period,
+            self.min_votes_required,
+            self.token,
+        );
+        if self.treasury > 0 {
+            fund_treasury(self.treasury);
+        }
+        dao
+    }
+}
@@ -0,0 +1,237 @@
+//! End-to-end tests against a node running `pallet-revive`, e.g. `ink-node` on the PATH or
+//! at `CONTRACTS_NODE`. Run with `cargo test --features e2e-tests --test e2e_tests`.
+#![cfg(feature = "e2e-tests")]
+
+use ink::env::{DefaultEnvironment, Event};
+use ink_e2e::events::{ContractEmitted, EventWithTopics};
+use ink_e2e::{ChainBackend, ContractsBackend, Sr25519Keyring};
+use simple_dao::*;
+
+type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+const TREASURY_FUNDS: u128 = 1_000_000_000_000;
+const REQUESTED: u128 = 400_000_000_000;
+
+// Helper function to get the address of a dev account
+fn address(account: Sr25519Keyring) -> ink::H160 {
+    ink_e2e::address::<DefaultEnvironment>(account)
+}
+
+// Helper function to check whether an event of type `E` was emitted
+fn emitted<E: Event>(events: &[EventWithTopics<ContractEmitted>]) -> bool {
+    events
+        .iter()
+        .any(|event| event.topics.first().map(|topic| topic.0) == E::SIGNATURE_TOPIC)
+}
+
+#[ink_e2e::test]
+async fn money_request_lifecycle_pays_out(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+    // given
+    let mut constructor = SimpleDaoRef::new(
+        vec![address(Sr25519Keyring::Alice), address(Sr25519Keyring::Bob)],
+        2000,
+        10, // voting period
+        2,  // min votes required
+    );
+    let contract = client
+        .instantiate("simple_dao", &ink_e2e::alice(), &mut constructor)
+        .submit()
+        .await
+        .expect("instantiate failed");
+    let mut dao = contract.call_builder::<SimpleDao>();
+
+    client
+        .call(&ink_e2e::alice(), &dao.deposit())
+        .value(TREASURY_FUNDS)
+        .submit()
+        .await
+        .expect("deposit failed");
+
+    // when
+    let create = client
+        .call(
+            &ink_e2e::alice(),
+            &dao.create_proposal(
+                "Fund Project".to_string(),
+                "Request funding for development".to_string(),
+                ProposalType::MoneyRequest,
+                vec!["Approve funding".to_string()],
+                Some(REQUESTED),
+            ),
+        )
+        .submit()
+        .await
+        .expect("create_proposal failed");
+    assert!(emitted::<ProposalCreated>(
+        &create.contract_emitted_events()?
+    ));
+    let proposal_id = create.return_value().expect("proposal rejected");
+
+    for voter in [ink_e2e::alice(), ink_e2e::bob()] {
+        let vote = client
+            .call(&voter, &dao.vote(proposal_id, 0))
+            .submit()
+            .await
+            .expect("vote failed");
+        assert!(emitted::<VoteCast>(&vote.contract_emitted_events()?));
+    }
+    let proposal = client
+        .call(&ink_e2e::alice(), &dao.get_proposal(proposal_id))
+        .dry_run()
+        .await?
+        .return_value()
+        .expect("proposal missing");
+    assert_eq!(proposal.status, ProposalStatus::Passed);
+
+    client
+        .call(&ink_e2e::alice(), &dao.execute_proposal(proposal_id))
+        .submit()
+        .await
+        .expect("execute_proposal failed")
+        .return_value()
+        .expect("execution rejected");
+
+    // then
+    let treasury_before = client.free_balance(contract.account_id).await?;
+    let claim = client
+        .call(&ink_e2e::alice(), &dao.claim_payout(None))
+        .submit()
+        .await
+        .expect("claim_payout failed");
+    assert!(emitted::<PayoutClaimed>(&claim.contract_emitted_events()?));
+    assert_eq!(claim.return_value(), Ok(REQUESTED));
+
+    let treasury_after = client.free_balance(contract.account_id).await?;
+    assert_eq!(treasury_before - treasury_after, REQUESTED);
+
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn unvoted_proposal_expires_on_finalize(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+    // given
+    let mut constructor = SimpleDaoRef::new(
+        vec![address(Sr25519Keyring::Alice), address(Sr25519Keyring::Bob)],
+        2000,
+        2, // voting period
+        1, // min votes required
+    );
+    let contract = client
+        .instantiate("simple_dao", &ink_e2e::alice(), &mut constructor)
+        .submit()
+        .await
+        .expect("instantiate failed");
+    let mut dao = contract.call_builder::<SimpleDao>();
+
+    let proposal_id = client
+        .call(
+            &ink_e2e::alice(),
+            &dao.create_proposal(
+                "Ignored".to_string(),
+                "Nobody votes on this".to_string(),
+                ProposalType::MultipleChoice,
+                vec!["Yes".to_string(), "No".to_string()],
+                None,
+            ),
+        )
+        .submit()
+        .await
+        .expect("create_proposal failed")
+        .return_value()
+        .expect("proposal rejected");
+
+    // when
+    // Every submitted extrinsic seals a block, deposits move the chain past the deadline
+    let finalize = dao.finalize_proposal(proposal_id);
+    while client
+        .call(&ink_e2e::bob(), &finalize)
+        .dry_run()
+        .await?
+        .return_value()
+        == Err(Error::VotingPeriodNotOver)
+    {
+        client
+            .call(&ink_e2e::bob(), &dao.deposit())
+            .value(1)
+            .submit()
+            .await
+            .expect("deposit failed");
+    }
+    let finalized = client
+        .call(&ink_e2e::bob(), &finalize)
+        .submit()
+        .await
+        .expect("finalize_proposal failed");
+
+    // then
+    assert!(emitted::<ProposalExpired>(
+        &finalized.contract_emitted_events()?
+    ));
+    let proposal = client
+        .call(&ink_e2e::alice(), &dao.get_proposal(proposal_id))
+        .dry_run()
+        .await?
+        .return_value()
+        .expect("proposal missing");
+    assert_eq!(proposal.status, ProposalStatus::Expired);
+
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn vote_weight_ignores_description_size(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+    // given
+    let mut constructor = SimpleDaoRef::new(
+        vec![address(Sr25519Keyring::Alice), address(Sr25519Keyring::Bob)],
+        2000,
+        10, // voting period
+        2,  // min votes required
+    );
+    let contract = client
+        .instantiate("simple_dao", &ink_e2e::alice(), &mut constructor)
+        .submit()
+        .await
+        .expect("instantiate failed");
+    let mut dao = contract.call_builder::<SimpleDao>();
+
+    let mut proposal_ids = Vec::new();
+    for description in ["Short".to_string(), "x".repeat(4096)] {
+        let proposal_id = client
+            .call(
+                &ink_e2e::alice(),
+                &dao.create_proposal(
+                    "Poll".to_string(),
+                    description,
+                    ProposalType::MultipleChoice,
+                    vec!["Yes".to_string(), "No".to_string()],
+                    None,
+                ),
+            )
+            .submit()
+            .await
+            .expect("create_proposal failed")
+            .return_value()
+            .expect("proposal rejected");
+        proposal_ids.push(proposal_id);
+    }
+
+    // when
+    let mut weights = Vec::new();
+    for proposal_id in proposal_ids {
+        let dry_run = client
+            .call(&ink_e2e::bob(), &dao.vote(proposal_id, 0))
+            .dry_run()
+            .await?;
+        assert_eq!(dry_run.return_value(), Ok(()));
+        weights.push(dry_run.exec_result.weight_required);
+    }
+
+    // then
+    // The description is stored apart, so its size neither reaches the proof nor,
+    // beyond noise, the execution time
+    assert_eq!(weights[0].proof_size(), weights[1].proof_size());
+    let tolerance = weights[0].ref_time() / 100;
+    assert!(weights[1].ref_time().abs_diff(weights[0].ref_time()) <= tolerance);
+
+    Ok(())
+}